@@ -45,7 +45,7 @@ async fn main(args: Args) -> anyhow::Result<()> {
 
 async fn add_todo(pool: &MySqlPool, description: String) -> anyhow::Result<u64> {
     // Insert the TODO, then obtain the ID of this row
-    sqlx::query!(
+    let id = sqlx::query!(
         r#"
 INSERT INTO todos ( description )
 VALUES ( ? )
@@ -53,13 +53,11 @@ VALUES ( ? )
         description
     )
     .execute(pool)
-    .await?;
-
-    let rec: (u64,) = sqlx::query_as("SELECT LAST_INSERT_ID()")
-        .fetch_one(pool)
-        .await?;
+    .await?
+    .last_insert_id()
+    .ok_or_else(|| anyhow::anyhow!("the TODO was not inserted"))?;
 
-    Ok(rec.0)
+    Ok(id as u64)
 }
 
 async fn complete_todo(pool: &MySqlPool, id: u64) -> anyhow::Result<bool> {
@@ -72,7 +70,8 @@ WHERE id = ?
         id
     )
     .execute(pool)
-    .await?;
+    .await?
+    .rows_affected();
 
     Ok(rows_affected > 0)
 }
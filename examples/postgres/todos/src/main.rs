@@ -67,7 +67,8 @@ WHERE id = $1
         id
     )
     .execute(&mut pool)
-    .await?;
+    .await?
+    .rows_affected();
 
     Ok(rows_affected > 0)
 }
@@ -45,7 +45,7 @@ async fn main(args: Args) -> anyhow::Result<()> {
 
 async fn add_todo(pool: &SqlitePool, description: String) -> anyhow::Result<i64> {
     // Insert the TODO, then obtain the ID of this row
-    sqlx::query!(
+    let id = sqlx::query!(
         r#"
 INSERT INTO todos ( description )
 VALUES ( $1 )
@@ -53,13 +53,11 @@ VALUES ( $1 )
         description
     )
     .execute(pool)
-    .await?;
-
-    let rec: (i64,) = sqlx::query_as("SELECT last_insert_rowid()")
-        .fetch_one(pool)
-        .await?;
+    .await?
+    .last_insert_id()
+    .ok_or_else(|| anyhow::anyhow!("the TODO was not inserted"))?;
 
-    Ok(rec.0)
+    Ok(id)
 }
 
 async fn complete_todo(pool: &SqlitePool, id: i64) -> anyhow::Result<bool> {
@@ -72,7 +70,8 @@ WHERE id = $1
         id
     )
     .execute(pool)
-    .await?;
+    .await?
+    .rows_affected();
 
     Ok(rows_affected > 0)
 }
@@ -0,0 +1,91 @@
+use crate::any::{Any, AnyType};
+use crate::arguments::Arguments;
+use crate::database::Database;
+use crate::encode::{Encode, IsNull};
+use crate::types::Type;
+
+#[derive(Debug, Clone)]
+pub enum AnyArgumentValue {
+    // A `NULL` is bound with the type of the value it stands in for, as Postgres and MySQL
+    // need a type for each parameter
+    Null(AnyType),
+
+    Bool(bool),
+
+    Integer(i32),
+
+    BigInt(i64),
+
+    Real(f32),
+
+    Double(f64),
+
+    Text(String),
+
+    Blob(Vec<u8>),
+}
+
+#[derive(Default)]
+pub struct AnyArguments {
+    values: Vec<AnyArgumentValue>,
+}
+
+impl AnyArguments {
+    // Binds each value to the arguments of the database in use
+    pub(crate) fn into_arguments<DB>(self) -> DB::Arguments
+    where
+        DB: Database,
+        bool: Type<DB> + Encode<DB>,
+        i32: Type<DB> + Encode<DB>,
+        i64: Type<DB> + Encode<DB>,
+        f32: Type<DB> + Encode<DB>,
+        f64: Type<DB> + Encode<DB>,
+        String: Type<DB> + Encode<DB>,
+        Vec<u8>: Type<DB> + Encode<DB>,
+    {
+        let mut arguments = DB::Arguments::default();
+        arguments.reserve(self.values.len(), 0);
+
+        for value in self.values {
+            match value {
+                AnyArgumentValue::Null(AnyType::Bool) => arguments.add(None::<bool>),
+                AnyArgumentValue::Null(AnyType::Integer) => arguments.add(None::<i32>),
+                AnyArgumentValue::Null(AnyType::BigInt) => arguments.add(None::<i64>),
+                AnyArgumentValue::Null(AnyType::Real) => arguments.add(None::<f32>),
+                AnyArgumentValue::Null(AnyType::Double) => arguments.add(None::<f64>),
+                AnyArgumentValue::Null(AnyType::Text) => arguments.add(None::<String>),
+                AnyArgumentValue::Null(AnyType::Blob) => arguments.add(None::<Vec<u8>>),
+                AnyArgumentValue::Bool(value) => arguments.add(value),
+                AnyArgumentValue::Integer(value) => arguments.add(value),
+                AnyArgumentValue::BigInt(value) => arguments.add(value),
+                AnyArgumentValue::Real(value) => arguments.add(value),
+                AnyArgumentValue::Double(value) => arguments.add(value),
+                AnyArgumentValue::Text(value) => arguments.add(value),
+                AnyArgumentValue::Blob(value) => arguments.add(value),
+            }
+        }
+
+        arguments
+    }
+}
+
+impl Arguments for AnyArguments {
+    type Database = Any;
+
+    fn reserve(&mut self, len: usize, _size_hint: usize) {
+        self.values.reserve(len);
+    }
+
+    fn add<T>(&mut self, value: T)
+    where
+        T: Encode<Self::Database> + Type<Self::Database>,
+    {
+        if let IsNull::Yes = value.encode_nullable(&mut self.values) {
+            // The type of a Rust value is always one of [AnyType]; anything else is bound as
+            // a `NULL` of text
+            let r#type = T::type_info().r#type().unwrap_or(AnyType::Text);
+
+            self.values.push(AnyArgumentValue::Null(r#type));
+        }
+    }
+}
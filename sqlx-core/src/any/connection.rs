@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+
+use futures_core::future::BoxFuture;
+
+use crate::any::executor::describe;
+use crate::any::options::AnyConnectOptionsKind;
+use crate::any::{Any, AnyConnectOptions};
+use crate::connection::{Connect, Connection};
+use crate::logging::LogSettings;
+use crate::pool::Credentials;
+use crate::statement::Statement;
+use crate::transaction::TransactionOptions;
+
+#[cfg(feature = "postgres")]
+use crate::any::executor::postgres_placeholders;
+
+#[cfg(feature = "mysql")]
+use crate::mysql::MySqlConnection;
+
+#[cfg(feature = "postgres")]
+use crate::postgres::PgConnection;
+
+#[cfg(feature = "sqlite")]
+use crate::sqlite::SqliteConnection;
+
+/// A connection to a **Postgres**, **MySQL** or **SQLite** database, chosen from the scheme of
+/// the connection URL.
+///
+/// | Scheme                    | Database       |
+/// |---------------------------|----------------|
+/// | `postgres`, `postgresql`  | Postgres       |
+/// | `mysql`, `mariadb`        | MySQL, MariaDB |
+/// | `sqlite`                  | SQLite         |
+///
+/// The driver of each database is only available if the feature of that database is enabled.
+/// The connection can also be configured with [`AnyConnectOptions`].
+pub struct AnyConnection(pub(crate) AnyConnectionKind);
+
+pub(crate) enum AnyConnectionKind {
+    #[cfg(feature = "postgres")]
+    Postgres(PgConnection),
+
+    #[cfg(feature = "mysql")]
+    MySql(MySqlConnection),
+
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteConnection),
+}
+
+impl AnyConnection {
+    pub(crate) fn id(&self) -> u64 {
+        delegate!(&self.0, conn => conn.id)
+    }
+
+    // Translates the `?` placeholders of a query to those of the database, if the query is
+    // prepared
+    #[cfg_attr(not(feature = "postgres"), allow(unused_variables))]
+    pub(crate) fn sql<'q>(&self, query: &'q str, prepared: bool) -> Cow<'q, str> {
+        match &self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(_) if prepared => Cow::Owned(postgres_placeholders(query)),
+
+            _ => Cow::Borrowed(query),
+        }
+    }
+}
+
+async fn connect(options: &AnyConnectOptions) -> crate::Result<AnyConnection> {
+    let kind = match &options.0 {
+        #[cfg(feature = "postgres")]
+        AnyConnectOptionsKind::Postgres(options) => {
+            AnyConnectionKind::Postgres(PgConnection::connect_with(options).await?)
+        }
+
+        #[cfg(feature = "mysql")]
+        AnyConnectOptionsKind::MySql(options) => {
+            AnyConnectionKind::MySql(MySqlConnection::connect_with(options).await?)
+        }
+
+        #[cfg(feature = "sqlite")]
+        AnyConnectOptionsKind::Sqlite(options) => {
+            AnyConnectionKind::Sqlite(SqliteConnection::connect_with(options).await?)
+        }
+    };
+
+    Ok(AnyConnection(kind))
+}
+
+impl Connect for AnyConnection {
+    type Options = AnyConnectOptions;
+
+    fn connect_with(options: &AnyConnectOptions) -> BoxFuture<'_, crate::Result<Self>> {
+        Box::pin(connect(options))
+    }
+
+    fn with_credentials(
+        options: &AnyConnectOptions,
+        credentials: &Credentials,
+    ) -> crate::Result<AnyConnectOptions> {
+        let kind = match &options.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectOptionsKind::Postgres(options) => AnyConnectOptionsKind::Postgres(
+                PgConnection::with_credentials(options, credentials)?,
+            ),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectOptionsKind::MySql(options) => AnyConnectOptionsKind::MySql(
+                MySqlConnection::with_credentials(options, credentials)?,
+            ),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectOptionsKind::Sqlite(options) => AnyConnectOptionsKind::Sqlite(
+                SqliteConnection::with_credentials(options, credentials)?,
+            ),
+        };
+
+        Ok(AnyConnectOptions(kind))
+    }
+}
+
+impl Connection for AnyConnection {
+    fn close(self) -> BoxFuture<'static, crate::Result<()>> {
+        delegate!(self.0, conn => conn.close())
+    }
+
+    fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
+        delegate!(&mut self.0, conn => conn.ping())
+    }
+
+    fn cached_statements_size(&self) -> usize {
+        delegate!(&self.0, conn => conn.cached_statements_size())
+    }
+
+    fn set_statement_cache_capacity(&mut self, capacity: usize) {
+        delegate!(&mut self.0, conn => conn.set_statement_cache_capacity(capacity))
+    }
+
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>> {
+        delegate!(&mut self.0, conn => conn.clear_cached_statements())
+    }
+
+    fn log_settings(&self) -> &LogSettings {
+        delegate!(&self.0, conn => conn.log_settings())
+    }
+
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        delegate!(&mut self.0, conn => conn.set_log_settings(settings))
+    }
+
+    fn prepare<'e>(&'e mut self, sql: &'e str) -> BoxFuture<'e, crate::Result<Statement<Any>>> {
+        Box::pin(async move {
+            let id = self.id();
+            let query = self.sql(sql, true);
+
+            let describe = delegate!(&mut self.0, conn => {
+                let statement = conn.prepare(&query).await?;
+
+                describe(statement.param_types(), statement.columns())
+            });
+
+            Ok(Statement::new(sql, id, describe))
+        })
+    }
+
+    fn is_broken(&self) -> bool {
+        delegate!(&self.0, conn => conn.is_broken())
+    }
+
+    fn in_transaction(&self) -> bool {
+        delegate!(&self.0, conn => conn.in_transaction())
+    }
+
+    #[doc(hidden)]
+    fn begin_statements(&self, options: &TransactionOptions) -> Vec<String> {
+        delegate!(&self.0, conn => conn.begin_statements(options))
+    }
+}
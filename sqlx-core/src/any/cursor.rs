@@ -0,0 +1,136 @@
+use futures_core::future::BoxFuture;
+
+use crate::any::connection::AnyConnectionKind;
+use crate::any::row::AnyRowKind;
+use crate::any::{Any, AnyArguments, AnyConnection, AnyRow};
+use crate::connection::ConnectionSource;
+use crate::cursor::Cursor;
+use crate::executor::Execute;
+use crate::pool::Pool;
+
+#[cfg(feature = "mysql")]
+use crate::mysql::cursor::{next_row as next_mysql_row, MySqlCursorState};
+#[cfg(feature = "mysql")]
+use crate::mysql::MySql;
+
+#[cfg(feature = "postgres")]
+use crate::postgres::cursor::{next_row as next_pg_row, PgCursorState};
+#[cfg(feature = "postgres")]
+use crate::postgres::Postgres;
+
+#[cfg(feature = "sqlite")]
+use crate::sqlite::cursor::{next_row as next_sqlite_row, SqliteCursorState};
+#[cfg(feature = "sqlite")]
+use crate::sqlite::Sqlite;
+
+pub struct AnyCursor<'c, 'q> {
+    source: ConnectionSource<'c, AnyConnection>,
+    query: Option<(&'q str, Option<AnyArguments>)>,
+    connection_id: Option<u64>,
+
+    // The cursor of the driver in use, created once the connection is known
+    state: Option<AnyCursorState<'q>>,
+}
+
+enum AnyCursorState<'q> {
+    #[cfg(feature = "postgres")]
+    Postgres(PgCursorState<'q>),
+
+    #[cfg(feature = "mysql")]
+    MySql(MySqlCursorState<'q>),
+
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteCursorState<'q>),
+}
+
+impl crate::cursor::private::Sealed for AnyCursor<'_, '_> {}
+
+impl<'c, 'q> Cursor<'c, 'q> for AnyCursor<'c, 'q> {
+    type Database = Any;
+
+    #[doc(hidden)]
+    fn from_pool<E>(pool: &Pool<AnyConnection>, query: E) -> Self
+    where
+        Self: Sized,
+        E: Execute<'q, Any>,
+    {
+        Self {
+            source: ConnectionSource::Pool(pool.clone()),
+            connection_id: query.connection_id(),
+            query: Some(query.into_parts()),
+            state: None,
+        }
+    }
+
+    #[doc(hidden)]
+    fn from_connection<E>(conn: &'c mut AnyConnection, query: E) -> Self
+    where
+        Self: Sized,
+        E: Execute<'q, Any>,
+    {
+        Self {
+            source: ConnectionSource::ConnectionRef(conn),
+            connection_id: query.connection_id(),
+            query: Some(query.into_parts()),
+            state: None,
+        }
+    }
+
+    fn next(&mut self) -> BoxFuture<crate::Result<Option<AnyRow<'_>>>> {
+        Box::pin(next(self))
+    }
+}
+
+async fn next<'a, 'c: 'a, 'q: 'a>(
+    cursor: &'a mut AnyCursor<'c, 'q>,
+) -> crate::Result<Option<AnyRow<'a>>> {
+    let conn = cursor.source.resolve().await?;
+
+    if let Some((query, arguments)) = cursor.query.take() {
+        let connection_id = cursor.connection_id;
+
+        cursor.state = Some(match &conn.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(_) => AnyCursorState::Postgres(PgCursorState::new(
+                conn.sql(query, arguments.is_some()),
+                arguments.map(AnyArguments::into_arguments::<Postgres>),
+                connection_id,
+            )),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(_) => AnyCursorState::MySql(MySqlCursorState::new(
+                query,
+                arguments.map(AnyArguments::into_arguments::<MySql>),
+                connection_id,
+            )),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(_) => AnyCursorState::Sqlite(SqliteCursorState::new(
+                query,
+                arguments.map(AnyArguments::into_arguments::<Sqlite>),
+                connection_id,
+            )),
+        });
+    }
+
+    let row = match (&mut conn.0, &mut cursor.state) {
+        #[cfg(feature = "postgres")]
+        (AnyConnectionKind::Postgres(conn), Some(AnyCursorState::Postgres(state))) => {
+            next_pg_row(conn, state).await?.map(AnyRowKind::Postgres)
+        }
+
+        #[cfg(feature = "mysql")]
+        (AnyConnectionKind::MySql(conn), Some(AnyCursorState::MySql(state))) => {
+            next_mysql_row(conn, state).await?.map(AnyRowKind::MySql)
+        }
+
+        #[cfg(feature = "sqlite")]
+        (AnyConnectionKind::Sqlite(conn), Some(AnyCursorState::Sqlite(state))) => {
+            next_sqlite_row(conn, state).await?.map(AnyRowKind::Sqlite)
+        }
+
+        _ => unreachable!("BUG: the cursor was created for another driver"),
+    };
+
+    Ok(row.map(AnyRow))
+}
@@ -0,0 +1,44 @@
+use crate::any::{
+    AnyArgumentValue, AnyArguments, AnyConnection, AnyCursor, AnyError, AnyRow, AnyTypeInfo,
+    AnyValue,
+};
+use crate::cursor::HasCursor;
+use crate::database::Database;
+use crate::row::HasRow;
+use crate::value::HasRawValue;
+
+/// **Any** database driver.
+#[derive(Debug)]
+pub struct Any;
+
+impl Database for Any {
+    type Connection = AnyConnection;
+
+    type Arguments = AnyArguments;
+
+    type TypeInfo = AnyTypeInfo;
+
+    type TableId = String;
+
+    type RawBuffer = Vec<AnyArgumentValue>;
+
+    type Error = AnyError;
+}
+
+impl<'c> HasRow<'c> for Any {
+    type Database = Any;
+
+    type Row = AnyRow<'c>;
+}
+
+impl<'c, 'q> HasCursor<'c, 'q> for Any {
+    type Database = Any;
+
+    type Cursor = AnyCursor<'c, 'q>;
+}
+
+impl<'c> HasRawValue<'c> for Any {
+    type Database = Any;
+
+    type RawValue = AnyValue<'c>;
+}
@@ -0,0 +1,95 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+
+use crate::error::{DatabaseError, ErrorKind};
+
+/// An error returned by the database behind an [`AnyConnection`](super::AnyConnection).
+///
+/// The errors of the connection are returned as [`Error::Database`](crate::Error::Database)
+/// with the error type of the driver in use (e.g. [`PgError`](crate::postgres::PgError)), so
+/// that they can be downcast as usual. This type wraps such an error where a single error type
+/// for every driver is needed.
+#[derive(Debug)]
+pub struct AnyError(Box<dyn DatabaseError>);
+
+impl AnyError {
+    /// Returns the error of the driver in use.
+    pub fn inner(&self) -> &dyn DatabaseError {
+        &*self.0
+    }
+
+    /// Returns the error of the driver in use.
+    pub fn into_inner(self) -> Box<dyn DatabaseError> {
+        self.0
+    }
+}
+
+impl From<Box<dyn DatabaseError>> for AnyError {
+    fn from(error: Box<dyn DatabaseError>) -> Self {
+        AnyError(error)
+    }
+}
+
+impl Display for AnyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl DatabaseError for AnyError {
+    fn message(&self) -> &str {
+        self.0.message()
+    }
+
+    fn code(&self) -> Option<&str> {
+        self.0.code()
+    }
+
+    fn details(&self) -> Option<&str> {
+        self.0.details()
+    }
+
+    fn hint(&self) -> Option<&str> {
+        self.0.hint()
+    }
+
+    fn table_name(&self) -> Option<&str> {
+        self.0.table_name()
+    }
+
+    fn column_name(&self) -> Option<&str> {
+        self.0.column_name()
+    }
+
+    fn constraint_name(&self) -> Option<&str> {
+        self.0.constraint_name()
+    }
+
+    fn kind(&self) -> ErrorKind {
+        self.0.kind()
+    }
+
+    fn as_ref_err(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_mut_err(&mut self) -> &mut (dyn StdError + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_box_err(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+        self
+    }
+}
+
+impl StdError for AnyError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(self.0.as_ref_err())
+    }
+}
+
+impl From<AnyError> for crate::Error {
+    fn from(err: AnyError) -> Self {
+        crate::Error::Database(err.0)
+    }
+}
@@ -0,0 +1,256 @@
+use futures_core::future::BoxFuture;
+
+use crate::any::connection::AnyConnectionKind;
+use crate::any::{Any, AnyArguments, AnyConnection, AnyCursor, AnyTypeInfo};
+use crate::cursor::Cursor;
+use crate::database::Database;
+use crate::describe::{Column, Describe};
+use crate::executor::{Execute, Executor, QueryResult, RefExecutor};
+
+#[cfg(feature = "mysql")]
+use crate::mysql::MySql;
+
+#[cfg(feature = "postgres")]
+use crate::postgres::Postgres;
+
+#[cfg(feature = "sqlite")]
+use crate::sqlite::Sqlite;
+
+// A query of an `AnyConnection`, forwarded to the driver in use
+struct Forward<'q, DB>
+where
+    DB: Database,
+{
+    query: &'q str,
+    arguments: Option<DB::Arguments>,
+    connection_id: Option<u64>,
+}
+
+impl<'q, DB> Execute<'q, DB> for Forward<'q, DB>
+where
+    DB: Database,
+{
+    fn into_parts(self) -> (&'q str, Option<DB::Arguments>) {
+        (self.query, self.arguments)
+    }
+
+    fn query_string(&self) -> &'q str {
+        self.query
+    }
+
+    fn connection_id(&self) -> Option<u64> {
+        self.connection_id
+    }
+}
+
+// Describes the parameters and columns of a query with the types of the driver in use
+pub(super) fn describe<DB>(
+    param_types: &[Option<DB::TypeInfo>],
+    columns: &[Column<DB>],
+) -> Describe<Any>
+where
+    DB: Database,
+    AnyTypeInfo: From<DB::TypeInfo>,
+{
+    Describe {
+        param_types: param_types
+            .iter()
+            .map(|ty| ty.clone().map(AnyTypeInfo::from))
+            .collect(),
+
+        result_columns: columns
+            .iter()
+            .map(|column| Column {
+                name: column.name.clone(),
+                table_id: column.table_id.as_ref().map(ToString::to_string),
+                type_info: column.type_info.clone().map(AnyTypeInfo::from),
+                non_null: column.non_null,
+            })
+            .collect(),
+    }
+}
+
+// Translates the `?` placeholders of a query to the numbered `$N` placeholders of Postgres,
+// leaving those in strings, quoted identifiers and comments alone
+#[cfg(feature = "postgres")]
+pub(crate) fn postgres_placeholders(query: &str) -> String {
+    let mut sql = String::with_capacity(query.len() + 8);
+    let mut index = 0;
+    let mut rest = query;
+
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '?' => {
+                index += 1;
+                sql.push('$');
+                sql.push_str(&index.to_string());
+                rest = &rest[1..];
+
+                continue;
+            }
+
+            '\'' | '"' => rest[1..].find(c).map_or(rest.len(), |end| end + 2),
+
+            '-' if rest.starts_with("--") => rest.find('\n').unwrap_or(rest.len()),
+
+            '/' if rest.starts_with("/*") => rest[2..].find("*/").map_or(rest.len(), |end| end + 4),
+
+            '$' => dollar_quoted(rest).unwrap_or(1),
+
+            _ => c.len_utf8(),
+        };
+
+        sql.push_str(&rest[..len]);
+        rest = &rest[len..];
+    }
+
+    sql
+}
+
+// Returns the length of the dollar-quoted string (e.g. `$tag$ .. $tag$`) at the start of `sql`
+#[cfg(feature = "postgres")]
+fn dollar_quoted(sql: &str) -> Option<usize> {
+    let tag_len = sql[1..].find(|c: char| !(c.is_alphanumeric() || c == '_'))? + 2;
+
+    // A `$` followed by digits is a numbered placeholder, not the start of a string
+    if !sql[tag_len - 1..].starts_with('$') || sql[1..tag_len - 1].starts_with(char::is_numeric) {
+        return None;
+    }
+
+    let tag = &sql[..tag_len];
+    let end = sql[tag_len..]
+        .find(tag)
+        .map_or(sql.len(), |end| tag_len + end + tag_len);
+
+    Some(end)
+}
+
+impl Executor for AnyConnection {
+    type Database = Any;
+
+    fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
+        &'c mut self,
+        query: E,
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        let connection_id = query.connection_id();
+        let (query, arguments) = query.into_parts();
+
+        Box::pin(async move {
+            let query = self.sql(query, arguments.is_some());
+
+            match &mut self.0 {
+                #[cfg(feature = "postgres")]
+                AnyConnectionKind::Postgres(conn) => {
+                    conn.execute(Forward {
+                        query: &query,
+                        arguments: arguments.map(AnyArguments::into_arguments::<Postgres>),
+                        connection_id,
+                    })
+                    .await
+                }
+
+                #[cfg(feature = "mysql")]
+                AnyConnectionKind::MySql(conn) => {
+                    conn.execute(Forward {
+                        query: &query,
+                        arguments: arguments.map(AnyArguments::into_arguments::<MySql>),
+                        connection_id,
+                    })
+                    .await
+                }
+
+                #[cfg(feature = "sqlite")]
+                AnyConnectionKind::Sqlite(conn) => {
+                    conn.execute(Forward {
+                        query: &query,
+                        arguments: arguments.map(AnyArguments::into_arguments::<Sqlite>),
+                        connection_id,
+                    })
+                    .await
+                }
+            }
+        })
+    }
+
+    fn fetch<'q, E>(&mut self, query: E) -> AnyCursor<'_, 'q>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        AnyCursor::from_connection(self, query)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q, E: 'e>(
+        &'e mut self,
+        query: E,
+    ) -> BoxFuture<'e, crate::Result<Describe<Self::Database>>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        Box::pin(async move {
+            let (query, _) = query.into_parts();
+            let query = self.sql(query, true);
+
+            delegate!(&mut self.0, conn => {
+                let described = conn.describe(&*query).await?;
+
+                Ok(describe(&described.param_types, &described.result_columns))
+            })
+        })
+    }
+}
+
+impl<'e> RefExecutor<'e> for &'e mut AnyConnection {
+    type Database = Any;
+
+    fn fetch_by_ref<'q, E>(self, query: E) -> AnyCursor<'e, 'q>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        AnyCursor::from_connection(self, query)
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::postgres_placeholders;
+
+    #[test]
+    fn it_numbers_placeholders() {
+        assert_eq!(
+            postgres_placeholders("SELECT * FROM users WHERE id = ? AND name = ?"),
+            "SELECT * FROM users WHERE id = $1 AND name = $2"
+        );
+    }
+
+    #[test]
+    fn it_skips_strings_identifiers_and_comments() {
+        assert_eq!(
+            postgres_placeholders(
+                "SELECT '?', \"a?\", ? -- why?\n, ? /* what? */ FROM \"t\" WHERE x = 'it''s?'"
+            ),
+            "SELECT '?', \"a?\", $1 -- why?\n, $2 /* what? */ FROM \"t\" WHERE x = 'it''s?'"
+        );
+    }
+
+    #[test]
+    fn it_skips_dollar_quoted_strings() {
+        assert_eq!(
+            postgres_placeholders("SELECT $$?$$, $tag$ ? $$ ? $tag$, ?, $1"),
+            "SELECT $$?$$, $tag$ ? $$ ? $tag$, $1, $1"
+        );
+    }
+
+    #[test]
+    fn it_keeps_unterminated_strings() {
+        assert_eq!(
+            postgres_placeholders("SELECT ?, 'oops ?"),
+            "SELECT $1, 'oops ?"
+        );
+        assert_eq!(postgres_placeholders("SELECT ? -- ?"), "SELECT $1 -- ?");
+        assert_eq!(postgres_placeholders("SELECT 'é', ?"), "SELECT 'é', $1");
+    }
+}
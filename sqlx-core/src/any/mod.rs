@@ -0,0 +1,55 @@
+//! **Any** database driver, selecting **Postgres**, **MySQL** or **SQLite** at runtime from
+//! the scheme of the connection URL.
+//!
+//! Queries use `?` for their parameters, which is translated to the placeholders of the
+//! database in use. Values can be bound and decoded for the types supported by every database;
+//! see [`types`] for the list.
+//!
+//! The methods of `query_as` for this driver come from [`AnyQueryAs`], which is not in
+//! `sqlx::prelude` as it would make them ambiguous for the other drivers; import it from here.
+
+// Forwards to the connection of the driver in use
+macro_rules! delegate {
+    ($kind:expr, $conn:ident => $body:expr) => {
+        match $kind {
+            #[cfg(feature = "postgres")]
+            crate::any::connection::AnyConnectionKind::Postgres($conn) => $body,
+
+            #[cfg(feature = "mysql")]
+            crate::any::connection::AnyConnectionKind::MySql($conn) => $body,
+
+            #[cfg(feature = "sqlite")]
+            crate::any::connection::AnyConnectionKind::Sqlite($conn) => $body,
+        }
+    };
+}
+
+mod arguments;
+mod connection;
+mod cursor;
+mod database;
+mod error;
+mod executor;
+mod options;
+mod row;
+mod type_info;
+pub mod types;
+mod value;
+
+pub use arguments::{AnyArgumentValue, AnyArguments};
+pub use connection::AnyConnection;
+pub use cursor::AnyCursor;
+pub use database::Any;
+pub use error::AnyError;
+pub use options::AnyConnectOptions;
+pub use row::AnyRow;
+pub use type_info::{AnyType, AnyTypeInfo};
+pub use value::AnyValue;
+
+/// An alias for [`Pool`][crate::pool::Pool], specialized for **Any** database.
+#[cfg_attr(docsrs, doc(cfg(feature = "any")))]
+pub type AnyPool = crate::pool::Pool<AnyConnection>;
+
+make_query_as!(AnyQueryAs, Any, AnyRow);
+impl_map_row_for_row!(Any, AnyRow);
+impl_from_row_for_tuples!(Any, AnyRow);
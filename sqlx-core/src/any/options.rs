@@ -0,0 +1,122 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use crate::url::Url;
+
+#[cfg(feature = "mysql")]
+use crate::mysql::MySqlConnectOptions;
+
+#[cfg(feature = "postgres")]
+use crate::postgres::PgConnectOptions;
+
+#[cfg(feature = "sqlite")]
+use crate::sqlite::SqliteConnectOptions;
+
+/// Options to connect to a **Postgres**, **MySQL** or **SQLite** database.
+///
+/// A value of `AnyConnectOptions` is parsed from a connection URL, with the database chosen
+/// from its scheme as described on [`AnyConnection`](crate::any::AnyConnection), or converted
+/// from the options of one of the databases:
+///
+/// ```rust,ignore
+/// # use sqlx_core::any::{AnyConnectOptions, AnyConnection};
+/// # use sqlx_core::connection::Connect;
+/// # use sqlx_core::postgres::PgConnectOptions;
+/// let options = AnyConnectOptions::from(PgConnectOptions::new().host("localhost"));
+/// let conn = AnyConnection::connect_with(&options).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnyConnectOptions(pub(crate) AnyConnectOptionsKind);
+
+#[derive(Debug, Clone)]
+pub(crate) enum AnyConnectOptionsKind {
+    #[cfg(feature = "postgres")]
+    Postgres(PgConnectOptions),
+
+    #[cfg(feature = "mysql")]
+    MySql(MySqlConnectOptions),
+
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteConnectOptions),
+}
+
+#[cfg(feature = "postgres")]
+impl From<PgConnectOptions> for AnyConnectOptions {
+    fn from(options: PgConnectOptions) -> Self {
+        AnyConnectOptions(AnyConnectOptionsKind::Postgres(options))
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<MySqlConnectOptions> for AnyConnectOptions {
+    fn from(options: MySqlConnectOptions) -> Self {
+        AnyConnectOptions(AnyConnectOptionsKind::MySql(options))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<SqliteConnectOptions> for AnyConnectOptions {
+    fn from(options: SqliteConnectOptions) -> Self {
+        AnyConnectOptions(AnyConnectOptionsKind::Sqlite(options))
+    }
+}
+
+impl TryFrom<&'_ Url> for AnyConnectOptions {
+    type Error = crate::Error;
+
+    fn try_from(url: &Url) -> crate::Result<Self> {
+        // The driver is chosen the same way as by `cargo sqlx` and the query macros
+        let kind = match url.scheme() {
+            #[cfg(feature = "postgres")]
+            "postgresql" | "postgres" => {
+                AnyConnectOptionsKind::Postgres(PgConnectOptions::try_from(url)?)
+            }
+
+            #[cfg(not(feature = "postgres"))]
+            "postgresql" | "postgres" => return Err(not_enabled("Postgres", "postgres")),
+
+            #[cfg(feature = "mysql")]
+            "mysql" | "mariadb" => {
+                AnyConnectOptionsKind::MySql(MySqlConnectOptions::try_from(url)?)
+            }
+
+            #[cfg(not(feature = "mysql"))]
+            "mysql" | "mariadb" => return Err(not_enabled("MySQL/MariaDB", "mysql")),
+
+            #[cfg(feature = "sqlite")]
+            "sqlite" => AnyConnectOptionsKind::Sqlite(SqliteConnectOptions::try_from(url)?),
+
+            #[cfg(not(feature = "sqlite"))]
+            "sqlite" => return Err(not_enabled("SQLite", "sqlite")),
+
+            scheme => {
+                return Err(crate::Error::Configuration(
+                    format!("unexpected scheme {:?} in the URL", scheme).into(),
+                ))
+            }
+        };
+
+        Ok(AnyConnectOptions(kind))
+    }
+}
+
+impl FromStr for AnyConnectOptions {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let url = Url::try_from(s)?;
+
+        Self::try_from(&url)
+    }
+}
+
+#[allow(dead_code)]
+fn not_enabled(database: &str, feature: &str) -> crate::Error {
+    crate::Error::Configuration(
+        format!(
+            "the URL has the scheme of a {} database but the `{}` feature of sqlx was not enabled",
+            database, feature
+        )
+        .into(),
+    )
+}
@@ -0,0 +1,93 @@
+use crate::any::value::AnyValueKind;
+use crate::any::{Any, AnyValue};
+use crate::row::{ColumnIndex, Row};
+
+#[cfg(feature = "mysql")]
+use crate::mysql::MySqlRow;
+
+#[cfg(feature = "postgres")]
+use crate::postgres::PgRow;
+
+#[cfg(feature = "sqlite")]
+use crate::sqlite::SqliteRow;
+
+pub struct AnyRow<'c>(pub(crate) AnyRowKind<'c>);
+
+pub(crate) enum AnyRowKind<'c> {
+    #[cfg(feature = "postgres")]
+    Postgres(PgRow<'c>),
+
+    #[cfg(feature = "mysql")]
+    MySql(MySqlRow<'c>),
+
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteRow<'c>),
+}
+
+impl crate::row::private_row::Sealed for AnyRow<'_> {}
+
+impl<'c> Row<'c> for AnyRow<'c> {
+    type Database = Any;
+
+    fn len(&self) -> usize {
+        match &self.0 {
+            #[cfg(feature = "postgres")]
+            AnyRowKind::Postgres(row) => row.len(),
+
+            #[cfg(feature = "mysql")]
+            AnyRowKind::MySql(row) => row.len(),
+
+            #[cfg(feature = "sqlite")]
+            AnyRowKind::Sqlite(row) => row.len(),
+        }
+    }
+
+    #[doc(hidden)]
+    fn try_get_raw<I>(&self, index: I) -> crate::Result<AnyValue<'c>>
+    where
+        I: ColumnIndex<'c, Self>,
+    {
+        let index = index.index(self)?;
+
+        Ok(AnyValue(match &self.0 {
+            #[cfg(feature = "postgres")]
+            AnyRowKind::Postgres(row) => AnyValueKind::Postgres(row.try_get_raw(index)?),
+
+            #[cfg(feature = "mysql")]
+            AnyRowKind::MySql(row) => AnyValueKind::MySql(row.try_get_raw(index)?),
+
+            #[cfg(feature = "sqlite")]
+            AnyRowKind::Sqlite(row) => AnyValueKind::Sqlite(row.try_get_raw(index)?),
+        }))
+    }
+}
+
+impl<'c> ColumnIndex<'c, AnyRow<'c>> for usize {
+    fn index(&self, row: &AnyRow<'c>) -> crate::Result<usize> {
+        match &row.0 {
+            #[cfg(feature = "postgres")]
+            AnyRowKind::Postgres(row) => self.index(row),
+
+            #[cfg(feature = "mysql")]
+            AnyRowKind::MySql(row) => self.index(row),
+
+            #[cfg(feature = "sqlite")]
+            AnyRowKind::Sqlite(row) => self.index(row),
+        }
+    }
+}
+
+impl<'c> ColumnIndex<'c, AnyRow<'c>> for str {
+    fn index(&self, row: &AnyRow<'c>) -> crate::Result<usize> {
+        match &row.0 {
+            #[cfg(feature = "postgres")]
+            AnyRowKind::Postgres(row) => self.index(row),
+
+            #[cfg(feature = "mysql")]
+            AnyRowKind::MySql(row) => self.index(row),
+
+            #[cfg(feature = "sqlite")]
+            AnyRowKind::Sqlite(row) => self.index(row),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt::{self, Display};
+
+use crate::types::TypeInfo;
+
+#[cfg(feature = "mysql")]
+use crate::mysql::MySqlTypeInfo;
+
+#[cfg(feature = "postgres")]
+use crate::postgres::PgTypeInfo;
+
+#[cfg(feature = "sqlite")]
+use crate::sqlite::SqliteTypeInfo;
+
+/// The kinds of values that can be bound to and decoded from every database.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AnyType {
+    Bool,
+    Integer,
+    BigInt,
+    Real,
+    Double,
+    Text,
+    Blob,
+}
+
+/// Type information of the **Any** driver.
+///
+/// The type of a Rust value is known before the database is; the type of a column or a
+/// parameter is that of the database in use.
+#[derive(Debug, Clone)]
+pub struct AnyTypeInfo(pub(crate) AnyTypeInfoKind);
+
+#[derive(Debug, Clone)]
+pub(crate) enum AnyTypeInfoKind {
+    Rust(AnyType),
+
+    #[cfg(feature = "postgres")]
+    Postgres(PgTypeInfo),
+
+    #[cfg(feature = "mysql")]
+    MySql(MySqlTypeInfo),
+
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteTypeInfo),
+}
+
+impl AnyTypeInfo {
+    pub(crate) const fn new(r#type: AnyType) -> Self {
+        Self(AnyTypeInfoKind::Rust(r#type))
+    }
+
+    /// Returns the kind of a Rust value, or `None` for a type of the database.
+    pub fn r#type(&self) -> Option<AnyType> {
+        match self.0 {
+            AnyTypeInfoKind::Rust(r#type) => Some(r#type),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+}
+
+impl Display for AnyTypeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            AnyTypeInfoKind::Rust(r#type) => f.write_str(match r#type {
+                AnyType::Bool => "BOOLEAN",
+                AnyType::Integer => "INTEGER",
+                AnyType::BigInt => "BIGINT",
+                AnyType::Real => "REAL",
+                AnyType::Double => "DOUBLE",
+                AnyType::Text => "TEXT",
+                AnyType::Blob => "BLOB",
+            }),
+
+            #[cfg(feature = "postgres")]
+            AnyTypeInfoKind::Postgres(ty) => Display::fmt(ty, f),
+
+            #[cfg(feature = "mysql")]
+            AnyTypeInfoKind::MySql(ty) => Display::fmt(ty, f),
+
+            #[cfg(feature = "sqlite")]
+            AnyTypeInfoKind::Sqlite(ty) => Display::fmt(ty, f),
+        }
+    }
+}
+
+impl PartialEq<AnyTypeInfo> for AnyTypeInfo {
+    fn eq(&self, other: &AnyTypeInfo) -> bool {
+        match (&self.0, &other.0) {
+            (AnyTypeInfoKind::Rust(a), AnyTypeInfoKind::Rust(b)) => a == b,
+
+            #[cfg(feature = "postgres")]
+            (AnyTypeInfoKind::Postgres(a), AnyTypeInfoKind::Postgres(b)) => a == b,
+
+            #[cfg(feature = "mysql")]
+            (AnyTypeInfoKind::MySql(a), AnyTypeInfoKind::MySql(b)) => a == b,
+
+            #[cfg(feature = "sqlite")]
+            (AnyTypeInfoKind::Sqlite(a), AnyTypeInfoKind::Sqlite(b)) => a == b,
+
+            _ => false,
+        }
+    }
+}
+
+impl TypeInfo for AnyTypeInfo {
+    fn compatible(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            // The type of a Rust value is checked against that of the database when the value
+            // is decoded
+            (AnyTypeInfoKind::Rust(_), _) | (_, AnyTypeInfoKind::Rust(_)) => true,
+
+            #[cfg(feature = "postgres")]
+            (AnyTypeInfoKind::Postgres(a), AnyTypeInfoKind::Postgres(b)) => a.compatible(b),
+
+            #[cfg(feature = "mysql")]
+            (AnyTypeInfoKind::MySql(a), AnyTypeInfoKind::MySql(b)) => a.compatible(b),
+
+            #[cfg(feature = "sqlite")]
+            (AnyTypeInfoKind::Sqlite(a), AnyTypeInfoKind::Sqlite(b)) => a.compatible(b),
+
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<PgTypeInfo> for AnyTypeInfo {
+    fn from(ty: PgTypeInfo) -> Self {
+        Self(AnyTypeInfoKind::Postgres(ty))
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<MySqlTypeInfo> for AnyTypeInfo {
+    fn from(ty: MySqlTypeInfo) -> Self {
+        Self(AnyTypeInfoKind::MySql(ty))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<SqliteTypeInfo> for AnyTypeInfo {
+    fn from(ty: SqliteTypeInfo) -> Self {
+        Self(AnyTypeInfoKind::Sqlite(ty))
+    }
+}
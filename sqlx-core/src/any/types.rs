@@ -0,0 +1,125 @@
+//! Conversions between Rust and the SQL types of **Any** database.
+//!
+//! # Types
+//!
+//! | Rust type                             | SQL type(s)                                          |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `bool`                                | BOOLEAN                                              |
+//! | `i32`                                 | INTEGER                                              |
+//! | `i64`                                 | BIGINT                                               |
+//! | `f32`                                 | REAL                                                 |
+//! | `f64`                                 | DOUBLE                                               |
+//! | `&str`, `String`                      | TEXT                                                 |
+//! | `&[u8]`, `Vec<u8>`                    | BLOB                                                 |
+//!
+//! A value is decoded by the driver in use, so it must be of a SQL type that the driver
+//! decodes as the Rust type.
+//!
+//! # Nullable
+//!
+//! In addition, `Option<T>` is supported where `T` implements `Type`. An `Option<T>` represents
+//! a potentially `NULL` value.
+//!
+
+use crate::any::value::AnyValueKind;
+use crate::any::{Any, AnyArgumentValue, AnyType, AnyTypeInfo, AnyValue};
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::types::{Type, TypeInfo};
+use crate::value::{HasRawValue, RawValue};
+
+#[cfg(feature = "mysql")]
+use crate::mysql::MySql;
+
+#[cfg(feature = "postgres")]
+use crate::postgres::Postgres;
+
+#[cfg(feature = "sqlite")]
+use crate::sqlite::Sqlite;
+
+macro_rules! impl_any_type {
+    ($ty:ty, $type:ident, |$value:ident| $encode:expr) => {
+        impl Type<Any> for $ty {
+            fn type_info() -> AnyTypeInfo {
+                AnyTypeInfo::new(AnyType::$type)
+            }
+        }
+
+        impl Encode<Any> for $ty {
+            fn encode(&self, buf: &mut Vec<AnyArgumentValue>) {
+                let $value = self;
+
+                buf.push($encode);
+            }
+        }
+    };
+}
+
+macro_rules! impl_any_decode {
+    ($lt:lifetime, $ty:ty) => {
+        impl<$lt> Decode<$lt, Any> for $ty {
+            fn decode(value: AnyValue<$lt>) -> crate::Result<Self> {
+                match value.0 {
+                    #[cfg(feature = "postgres")]
+                    AnyValueKind::Postgres(value) => decode::<Postgres, Self>(value),
+
+                    #[cfg(feature = "mysql")]
+                    AnyValueKind::MySql(value) => decode::<MySql, Self>(value),
+
+                    #[cfg(feature = "sqlite")]
+                    AnyValueKind::Sqlite(value) => decode::<Sqlite, Self>(value),
+                }
+            }
+        }
+    };
+}
+
+impl_any_type!(bool, Bool, |value| AnyArgumentValue::Bool(*value));
+impl_any_type!(i32, Integer, |value| AnyArgumentValue::Integer(*value));
+impl_any_type!(i64, BigInt, |value| AnyArgumentValue::BigInt(*value));
+impl_any_type!(f32, Real, |value| AnyArgumentValue::Real(*value));
+impl_any_type!(f64, Double, |value| AnyArgumentValue::Double(*value));
+impl_any_type!(str, Text, |value| AnyArgumentValue::Text(value.to_owned()));
+impl_any_type!(String, Text, |value| AnyArgumentValue::Text(value.clone()));
+impl_any_type!([u8], Blob, |value| AnyArgumentValue::Blob(value.to_vec()));
+impl_any_type!(Vec<u8>, Blob, |value| AnyArgumentValue::Blob(value.clone()));
+
+impl_any_decode!('de, bool);
+impl_any_decode!('de, i32);
+impl_any_decode!('de, i64);
+impl_any_decode!('de, f32);
+impl_any_decode!('de, f64);
+impl_any_decode!('de, &'de str);
+impl_any_decode!('de, String);
+impl_any_decode!('de, &'de [u8]);
+impl_any_decode!('de, Vec<u8>);
+
+impl<'de, T> Decode<'de, Any> for Option<T>
+where
+    T: Decode<'de, Any>,
+{
+    fn decode(value: AnyValue<'de>) -> crate::Result<Self> {
+        if value.is_null() {
+            Ok(None)
+        } else {
+            <T as Decode<Any>>::decode(value).map(Some)
+        }
+    }
+}
+
+// Decodes a value with the driver in use, after checking that its type is compatible with the
+// type of the Rust value on that database
+fn decode<'de, DB, T>(value: <DB as HasRawValue<'de>>::RawValue) -> crate::Result<T>
+where
+    DB: Database,
+    T: Type<DB> + Decode<'de, DB>,
+{
+    if let Some(ty) = value.type_info() {
+        if !ty.compatible(&T::type_info()) {
+            return Err(crate::Error::mismatched_types::<DB, T>(ty));
+        }
+    }
+
+    T::decode(value)
+}
@@ -0,0 +1,57 @@
+use crate::any::{Any, AnyTypeInfo};
+use crate::value::RawValue;
+
+#[cfg(feature = "mysql")]
+use crate::mysql::MySqlValue;
+
+#[cfg(feature = "postgres")]
+use crate::postgres::PgValue;
+
+#[cfg(feature = "sqlite")]
+use crate::sqlite::SqliteValue;
+
+pub struct AnyValue<'c>(pub(crate) AnyValueKind<'c>);
+
+pub(crate) enum AnyValueKind<'c> {
+    #[cfg(feature = "postgres")]
+    Postgres(PgValue<'c>),
+
+    #[cfg(feature = "mysql")]
+    MySql(MySqlValue<'c>),
+
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteValue<'c>),
+}
+
+impl<'c> AnyValue<'c> {
+    /// Returns true if the value is `NULL`.
+    pub(crate) fn is_null(&self) -> bool {
+        match &self.0 {
+            #[cfg(feature = "postgres")]
+            AnyValueKind::Postgres(value) => value.get().is_none(),
+
+            #[cfg(feature = "mysql")]
+            AnyValueKind::MySql(value) => value.get().is_none(),
+
+            #[cfg(feature = "sqlite")]
+            AnyValueKind::Sqlite(value) => value.is_null(),
+        }
+    }
+}
+
+impl<'c> RawValue<'c> for AnyValue<'c> {
+    type Database = Any;
+
+    fn type_info(&self) -> Option<AnyTypeInfo> {
+        match &self.0 {
+            #[cfg(feature = "postgres")]
+            AnyValueKind::Postgres(value) => value.type_info().map(Into::into),
+
+            #[cfg(feature = "mysql")]
+            AnyValueKind::MySql(value) => value.type_info().map(Into::into),
+
+            #[cfg(feature = "sqlite")]
+            AnyValueKind::Sqlite(value) => value.type_info().map(Into::into),
+        }
+    }
+}
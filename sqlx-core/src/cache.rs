@@ -0,0 +1,151 @@
+//! A bounded, least-recently-used cache of prepared statements.
+//!
+//! Each connection caches the statements it prepares, keyed by query string, so repeated
+//! queries skip the round-trip to the server. Without a bound, applications that build
+//! dynamic SQL would hold an ever-growing number of prepared statements on the server
+//! for the lifetime of the connection.
+
+use std::collections::HashMap;
+
+// The number of statements each connection caches by default
+pub(crate) const DEFAULT_CAPACITY: usize = 100;
+
+pub(crate) struct StatementCache<T> {
+    inner: HashMap<Box<str>, (u64, T)>,
+    capacity: usize,
+
+    // Incremented on each access; the entry with the lowest tick is the least recently used
+    tick: u64,
+}
+
+#[allow(dead_code)]
+impl<T> StatementCache<T> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            inner: HashMap::new(),
+            capacity: capacity.max(1),
+            tick: 0,
+        }
+    }
+
+    /// Returns the cached value for the query, marking it as most recently used.
+    pub(crate) fn get_mut(&mut self, query: &str) -> Option<&mut T> {
+        self.tick += 1;
+
+        let tick = self.tick;
+
+        self.inner.get_mut(query).map(|(used, value)| {
+            *used = tick;
+            value
+        })
+    }
+
+    /// Returns the cached value for the query without affecting its recency.
+    pub(crate) fn peek(&self, query: &str) -> Option<&T> {
+        self.inner.get(query).map(|(_, value)| value)
+    }
+
+    /// Inserts a value as the most recently used. If the cache is full, the least recently
+    /// used values are removed and returned so that the caller can release them.
+    pub(crate) fn insert(&mut self, query: &str, value: T) -> Vec<T> {
+        let mut evicted = Vec::new();
+
+        if let Some(previous) = self.inner.remove(query) {
+            evicted.push(previous.1);
+        }
+
+        while self.inner.len() >= self.capacity {
+            match self.remove_lru() {
+                Some(value) => evicted.push(value),
+                None => break,
+            }
+        }
+
+        self.tick += 1;
+        self.inner.insert(query.into(), (self.tick, value));
+
+        evicted
+    }
+
+    fn remove_lru(&mut self) -> Option<T> {
+        let query = self
+            .inner
+            .iter()
+            .min_by_key(|(_, (used, _))| *used)
+            .map(|(query, _)| query.clone())?;
+
+        self.inner.remove(&query).map(|(_, value)| value)
+    }
+
+    /// Removes all values from the cache, returning them.
+    pub(crate) fn clear(&mut self) -> Vec<T> {
+        self.inner.drain().map(|(_, (_, value))| value).collect()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the capacity of the cache. If the cache is larger than the new capacity, it is
+    /// trimmed on the next insert.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatementCache;
+
+    #[test]
+    fn it_evicts_the_least_recently_used() {
+        let mut cache = StatementCache::new(2);
+
+        assert!(cache.insert("SELECT 1", 1).is_empty());
+        assert!(cache.insert("SELECT 2", 2).is_empty());
+
+        // touch 1 so that 2 becomes the least recently used
+        assert_eq!(cache.get_mut("SELECT 1"), Some(&mut 1));
+
+        assert_eq!(cache.insert("SELECT 3", 3), vec![2]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.peek("SELECT 2"), None);
+        assert_eq!(cache.peek("SELECT 1"), Some(&1));
+        assert_eq!(cache.peek("SELECT 3"), Some(&3));
+    }
+
+    #[test]
+    fn it_trims_after_shrinking() {
+        let mut cache = StatementCache::new(3);
+
+        cache.insert("SELECT 1", 1);
+        cache.insert("SELECT 2", 2);
+        cache.insert("SELECT 3", 3);
+
+        cache.set_capacity(1);
+
+        let mut evicted = cache.insert("SELECT 4", 4);
+        evicted.sort();
+
+        assert_eq!(evicted, vec![1, 2, 3]);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn it_clears() {
+        let mut cache = StatementCache::new(3);
+
+        cache.insert("SELECT 1", 1);
+        cache.insert("SELECT 2", 2);
+
+        let mut cleared = cache.clear();
+        cleared.sort();
+
+        assert_eq!(cleared, vec![1, 2]);
+        assert_eq!(cache.len(), 0);
+    }
+}
@@ -1,12 +1,19 @@
 //! Contains the `Connection` and `Connect` traits.
 
 use std::convert::TryInto;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::task::Poll;
 
 use futures_core::future::BoxFuture;
+use futures_util::future::poll_fn;
 
 use crate::executor::Executor;
-use crate::pool::{Pool, PoolConnection};
-use crate::transaction::Transaction;
+use crate::logging::LogSettings;
+use crate::pool::{Credentials, Pool, PoolConnection};
+use crate::statement::Statement;
+use crate::transaction::{Transaction, TransactionOptions};
 use crate::url::Url;
 
 /// Represents a single database connection rather than a pool of database connections.
@@ -43,6 +50,52 @@ where
         Box::pin(Transaction::new(0, self))
     }
 
+    /// Starts a new transaction with the given isolation level and access mode.
+    ///
+    /// See [`TransactionOptions`] for how the options are applied by each database. On a
+    /// [`Transaction`], this creates a save point instead, which cannot have options of its own.
+    fn begin_with(
+        self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'static, crate::Result<Transaction<Self>>>
+    where
+        Self: Sized,
+    {
+        Box::pin(Transaction::with_options(self, options))
+    }
+
+    /// Runs `f` in a transaction on this connection.
+    ///
+    /// The transaction is committed if `f` returns `Ok`, and rolled back if it returns `Err` or
+    /// panics (after which the panic is resumed). On a [`Transaction`], `f` runs in a save point
+    /// instead.
+    ///
+    /// ```rust,ignore
+    /// let id = conn
+    ///     .transaction(|conn| {
+    ///         Box::pin(async move {
+    ///             sqlx::query("INSERT INTO users (name) VALUES ('Herp Derpinson')")
+    ///                 .execute(conn)
+    ///                 .await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    fn transaction<'c, F, T>(&'c mut self, f: F) -> BoxFuture<'c, crate::Result<T>>
+    where
+        Self: Sized,
+        F: for<'t> FnOnce(&'t mut Self) -> BoxFuture<'t, crate::Result<T>> + Send + 'c,
+        T: Send + 'c,
+    {
+        Box::pin(async move {
+            let begin = self.begin_statements(&TransactionOptions::default());
+
+            run_in_transaction(self, begin, "COMMIT", "ROLLBACK", f)
+                .await
+                .unwrap_or_else(|payload| panic::resume_unwind(payload))
+        })
+    }
+
     /// Explicitly close this database connection.
     ///
     /// This method is **not required** for safe and consistent operation. However, it is
@@ -52,15 +105,160 @@ where
 
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<crate::Result<()>>;
+
+    /// The number of prepared statements currently cached in the connection.
+    fn cached_statements_size(&self) -> usize;
+
+    /// Sets the maximum number of prepared statements cached in the connection.
+    ///
+    /// When the cache is full, the least recently used statement is closed to make room for a
+    /// new one. If the cache currently holds more statements than `capacity`, the excess is
+    /// closed the next time a statement is prepared. A `capacity` of `0` is treated as `1`.
+    ///
+    /// Defaults to `100`, or the value of the `statement-cache-capacity` parameter in the
+    /// connection URL.
+    fn set_statement_cache_capacity(&mut self, capacity: usize);
+
+    /// Removes all statements from the connection's cache, closing them on the server.
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>>;
+
+    /// The settings for the logging of queries executed on this connection.
+    fn log_settings(&self) -> &LogSettings;
+
+    /// Changes how queries executed on this connection are logged.
+    fn set_log_settings(&mut self, settings: LogSettings);
+
+    /// Prepares a statement on this connection, returning a [`Statement`] that describes its
+    /// parameters and result columns and can be executed repeatedly on this connection.
+    ///
+    /// ```rust,ignore
+    /// let statement = conn.prepare("SELECT id, name FROM users WHERE id = $1").await?;
+    ///
+    /// assert_eq!(statement.columns()[1].name.as_deref(), Some("name"));
+    ///
+    /// let row = statement.query().bind(1_i32).fetch_one(&mut conn).await?;
+    /// ```
+    fn prepare<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, crate::Result<Statement<Self::Database>>>;
+
+    /// Whether this connection can no longer be used, e.g. because it was lost or its server
+    /// shut down.
+    ///
+    /// A [`Pool`] closes a broken connection instead of taking it back. Returns `false` unless
+    /// the database tracks this.
+    fn is_broken(&self) -> bool {
+        false
+    }
+
+    /// Whether a transaction is open on this connection, as last reported by the database.
+    ///
+    /// This includes transactions started with a plain `BEGIN` statement instead of
+    /// [`begin`](Connection::begin).
+    fn in_transaction(&self) -> bool;
+
+    /// The statements that begin a transaction with the given options on this connection.
+    #[doc(hidden)]
+    fn begin_statements(&self, options: &TransactionOptions) -> Vec<String>;
+}
+
+// Runs `f` between the statements that begin and end a transaction or save point, rolling it
+// back if `f` returns `Err` or panics. The payload of a panic is returned to be resumed.
+pub(crate) async fn run_in_transaction<C, F, T>(
+    conn: &mut C,
+    begin: Vec<String>,
+    commit: &str,
+    rollback: &str,
+    f: F,
+) -> std::thread::Result<crate::Result<T>>
+where
+    C: Connection,
+    F: for<'t> FnOnce(&'t mut C) -> BoxFuture<'t, crate::Result<T>>,
+{
+    for statement in begin {
+        if let Err(error) = conn.execute(&*statement).await {
+            return Ok(Err(error));
+        }
+    }
+
+    let result = {
+        let mut future = f(conn);
+
+        // A panic while polling is caught so that the transaction can be rolled back
+        poll_fn(
+            |cx| match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+                Ok(Poll::Ready(result)) => Poll::Ready(Ok(result)),
+                Ok(Poll::Pending) => Poll::Pending,
+                Err(payload) => Poll::Ready(Err(payload)),
+            },
+        )
+        .await
+    };
+
+    match result {
+        Ok(Ok(value)) => Ok(conn.execute(commit).await.map(|_| value)),
+
+        Ok(Err(error)) => {
+            // The error of the transaction is more useful than a failure to roll it back
+            let _ = conn.execute(rollback).await;
+
+            Ok(Err(error))
+        }
+
+        Err(payload) => {
+            let _ = conn.execute(rollback).await;
+
+            Err(payload)
+        }
+    }
 }
 
 /// Represents a type that can directly establish a new connection.
 pub trait Connect: Connection {
+    /// The options used to establish a new connection.
+    ///
+    /// The options can be built programmatically or parsed from a connection URL with
+    /// [`FromStr`].
+    type Options: FromStr<Err = crate::Error> + Clone + Debug + Send + Sync + 'static;
+
     /// Establish a new database connection.
+    ///
+    /// The URL is parsed into [`Options`](Connect::Options) and passed to
+    /// [`connect_with`](Connect::connect_with).
     fn connect<T>(url: T) -> BoxFuture<'static, crate::Result<Self>>
     where
         T: TryInto<Url, Error = url::ParseError>,
+        Self: Sized,
+    {
+        let url = url.try_into();
+
+        Box::pin(async move {
+            let options = url?.as_str().parse::<Self::Options>()?;
+
+            Self::connect_with(&options).await
+        })
+    }
+
+    /// Establish a new database connection with the provided options.
+    fn connect_with(options: &Self::Options) -> BoxFuture<'_, crate::Result<Self>>
+    where
         Self: Sized;
+
+    /// Returns the options with the username and password replaced by those of `credentials`,
+    /// where they are set.
+    ///
+    /// Returns an error if the database does not authenticate with a username and password.
+    fn with_credentials(
+        options: &Self::Options,
+        credentials: &Credentials,
+    ) -> crate::Result<Self::Options> {
+        let _ = (options, credentials);
+
+        Err(crate::Error::Configuration(
+            "the database does not support credentials".into(),
+        ))
+    }
 }
 
 #[allow(dead_code)]
@@ -31,6 +31,10 @@ pub enum Error {
     /// Connection URL was malformed.
     UrlParse(url::ParseError),
 
+    /// Connection options were invalid (e.g., an unrecognized value for a query parameter
+    /// in the connection URL).
+    Configuration(Box<dyn StdError + Send + Sync>),
+
     /// An error was returned by the database.
     Database(Box<dyn DatabaseError>),
 
@@ -58,7 +62,11 @@ pub enum Error {
     /// A [`Pool::acquire`] timed out due to connections not becoming available or
     /// because another task encountered too many errors while trying to open a new connection.
     ///
+    /// The error returned from `Pool::acquire` holds a [`PoolTimeout`] with the state of the
+    /// pool and the last error from opening a connection.
+    ///
     /// [`Pool::acquire`]: crate::pool::Pool::acquire
+    /// [`PoolTimeout`]: crate::pool::PoolTimeout
     PoolTimedOut(Option<Box<dyn StdError + Send + Sync>>),
 
     /// [`Pool::close`] was called while we were waiting in [`Pool::acquire`].
@@ -67,12 +75,27 @@ pub enum Error {
     /// [`Pool::close`]: crate::pool::Pool::close
     PoolClosed,
 
+    /// A query created from a [`Statement`] was executed on a connection other than the
+    /// one that prepared it.
+    ///
+    /// [`Statement`]: crate::statement::Statement
+    StatementConnectionMismatch,
+
     /// An error occurred while attempting to setup TLS.
     /// This should only be returned from an explicit ask for TLS.
     Tls(Box<dyn StdError + Send + Sync>),
 
     /// An error occurred decoding data received from the database.
     Decode(Box<dyn StdError + Send + Sync>),
+
+    /// An error occurred decoding the value of a column of a row.
+    ///
+    /// `index` is the name (quoted) or position of the column. If the type of the column was
+    /// not compatible with the Rust type it was decoded as, the message of `source` names both.
+    ColumnDecode {
+        index: String,
+        source: Box<dyn StdError + Send + Sync>,
+    },
 }
 
 impl Error {
@@ -98,6 +121,26 @@ impl Error {
             expected
         );
     }
+
+    /// Attaches the column at `index` to an error that occurred while decoding its value.
+    ///
+    /// A [`Decode`](Error::Decode) error becomes a [`ColumnDecode`](Error::ColumnDecode)
+    /// error, and the column of a `ColumnDecode` error is replaced. Other errors are
+    /// returned unchanged.
+    #[doc(hidden)]
+    pub fn in_column<I>(self, index: I) -> Self
+    where
+        I: Debug,
+    {
+        match self {
+            Error::Decode(source) | Error::ColumnDecode { source, .. } => Error::ColumnDecode {
+                index: format!("{:?}", index),
+                source,
+            },
+
+            error => error,
+        }
+    }
 }
 
 impl StdError for Error {
@@ -105,8 +148,10 @@ impl StdError for Error {
         match self {
             Error::Io(error) => Some(error),
             Error::UrlParse(error) => Some(error),
+            Error::Configuration(error) => Some(&**error),
             Error::PoolTimedOut(Some(error)) => Some(&**error),
             Error::Decode(error) => Some(&**error),
+            Error::ColumnDecode { source, .. } => Some(&**source),
             Error::Tls(error) => Some(&**error),
             Error::Database(error) => Some(error.as_ref_err()),
 
@@ -124,8 +169,16 @@ impl Display for Error {
 
             Error::UrlParse(error) => write!(f, "{}", error),
 
+            Error::Configuration(error) => write!(f, "invalid connection options: {}", error),
+
             Error::Decode(error) => write!(f, "{}", error),
 
+            Error::ColumnDecode { index, source } => write!(
+                f,
+                "error occurred while decoding column {}: {}",
+                index, source
+            ),
+
             Error::Database(error) => Display::fmt(error, f),
 
             Error::RowNotFound => f.write_str("found no row when we expected at least one"),
@@ -152,6 +205,10 @@ impl Display for Error {
 
             Error::PoolClosed => f.write_str("attempted to acquire a connection on a closed pool"),
 
+            Error::StatementConnectionMismatch => f.write_str(
+                "attempted to execute a prepared statement on a connection that did not prepare it",
+            ),
+
             Error::Tls(ref err) => write!(f, "error during TLS upgrade: {}", err),
         }
     }
@@ -238,6 +295,11 @@ pub trait DatabaseError: StdError + Send + Sync + 'static {
         None
     }
 
+    /// The kind of the error, classified from its database-specific code.
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+
     #[doc(hidden)]
     fn as_ref_err(&self) -> &(dyn StdError + Send + Sync + 'static);
 
@@ -248,6 +310,39 @@ pub trait DatabaseError: StdError + Send + Sync + 'static {
     fn into_box_err(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static>;
 }
 
+/// The kind of an error returned by the database.
+///
+/// This is the same for each database, so that e.g. a unique constraint violation can be
+/// detected without matching on Postgres' SQLSTATE `23505` and MySQL's error number `1062`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A unique or primary key constraint was violated.
+    UniqueViolation,
+
+    /// A foreign key constraint was violated.
+    ForeignKeyViolation,
+
+    /// A `NULL` was written to a column that does not allow it.
+    NotNullViolation,
+
+    /// A check constraint was violated.
+    CheckViolation,
+
+    /// A transaction could not be serialized with concurrent transactions and should
+    /// be retried.
+    SerializationFailure,
+
+    /// A deadlock was detected; the transaction was rolled back and should be retried.
+    Deadlock,
+
+    /// A lock could not be acquired in time.
+    LockTimeout,
+
+    /// Any other error.
+    Other,
+}
+
 impl dyn DatabaseError {
     /// Downcast this `&dyn DatabaseError` to a specific database error type:
     ///
@@ -373,8 +468,9 @@ macro_rules! tls_err {
 
 /// An unexpected `NULL` was encountered during decoding.
 ///
-/// Returned from `Row::get` if the value from the database is `NULL`
-/// and you are not decoding into an `Option`.
+/// Returned from `Row::try_get` if the value from the database is `NULL`
+/// and you are not decoding into an `Option`. It is the `source` of an
+/// [`Error::ColumnDecode`] which names the column.
 #[derive(Debug, Clone, Copy)]
 pub struct UnexpectedNullError;
 
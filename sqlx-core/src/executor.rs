@@ -24,11 +24,12 @@ where
     /// Executes the query for its side-effects and
     /// discarding any potential result rows.
     ///
-    /// Returns the number of rows affected, or 0 if not applicable.
+    /// Returns the number of rows affected and, where the database reports it, the ID of the
+    /// last inserted row.
     fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
         &'c mut self,
         query: E,
-    ) -> BoxFuture<'e, crate::Result<u64>>
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
     where
         E: Execute<'q, Self::Database>;
 
@@ -83,6 +84,85 @@ where
     /// Returns the query string, without any parameters replaced.
     #[doc(hidden)]
     fn query_string(&self) -> &'q str;
+
+    /// Returns the connection that prepared this query, if it was created from a
+    /// [`Statement`](crate::statement::Statement).
+    #[doc(hidden)]
+    fn connection_id(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// The result of [`Executor::execute`].
+///
+/// A query string may contain several statements (when it is executed without arguments); the
+/// result of each is available from [`statements`](QueryResult::statements).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryResult {
+    statements: Vec<StatementResult>,
+}
+
+impl QueryResult {
+    /// The number of rows inserted, updated or deleted by all statements of the query.
+    pub fn rows_affected(&self) -> u64 {
+        self.statements
+            .iter()
+            .map(|statement| statement.rows_affected)
+            .sum()
+    }
+
+    /// The ID of the last row inserted by the query, if any.
+    ///
+    /// This is the `AUTO_INCREMENT` value on MySQL and the `ROWID` on SQLite. Postgres does
+    /// not report inserted IDs; use `RETURNING` instead.
+    pub fn last_insert_id(&self) -> Option<i64> {
+        self.statements
+            .iter()
+            .rev()
+            .find_map(|statement| statement.last_insert_id)
+    }
+
+    /// The command tag of the last statement of the query, e.g. `INSERT` or `UPDATE`.
+    ///
+    /// This is only reported by Postgres.
+    pub fn command(&self) -> Option<&str> {
+        self.statements.last()?.command()
+    }
+
+    /// The results of the individual statements of the query, in order.
+    pub fn statements(&self) -> &[StatementResult] {
+        &self.statements
+    }
+
+    pub(crate) fn push(&mut self, statement: StatementResult) {
+        self.statements.push(statement);
+    }
+}
+
+/// The result of one statement of a query, returned by [`QueryResult::statements`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatementResult {
+    pub(crate) rows_affected: u64,
+    pub(crate) last_insert_id: Option<i64>,
+    pub(crate) command: Option<Box<str>>,
+}
+
+impl StatementResult {
+    /// The number of rows inserted, updated or deleted by the statement.
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+
+    /// The ID of the row inserted by the statement, if any.
+    pub fn last_insert_id(&self) -> Option<i64> {
+        self.last_insert_id
+    }
+
+    /// The command tag of the statement, e.g. `INSERT` or `CREATE TABLE`. Only reported
+    /// by Postgres.
+    pub fn command(&self) -> Option<&str> {
+        self.command.as_deref()
+    }
 }
 
 impl<'q, DB> Execute<'q, DB> for &'q str
@@ -110,7 +190,7 @@ where
     fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
         &'c mut self,
         query: E,
-    ) -> BoxFuture<'e, crate::Result<u64>>
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
     where
         E: Execute<'q, Self::Database>,
     {
@@ -13,7 +13,7 @@ pub struct MaybeTlsStream {
 
 enum Inner {
     NotTls(TcpStream),
-    #[cfg(all(feature = "postgres", unix))]
+    #[cfg(all(any(feature = "postgres", feature = "mysql"), unix))]
     UnixStream(crate::runtime::UnixStream),
     #[cfg(feature = "tls")]
     Tls(async_native_tls::TlsStream<TcpStream>),
@@ -22,7 +22,7 @@ enum Inner {
 }
 
 impl MaybeTlsStream {
-    #[cfg(all(feature = "postgres", unix))]
+    #[cfg(all(any(feature = "postgres", feature = "mysql"), unix))]
     pub async fn connect_uds<S: AsRef<std::ffi::OsStr>>(p: S) -> crate::Result<Self> {
         let conn = crate::runtime::UnixStream::connect(p.as_ref()).await?;
         Ok(Self {
@@ -40,7 +40,7 @@ impl MaybeTlsStream {
     pub fn is_tls(&self) -> bool {
         match self.inner {
             Inner::NotTls(_) => false,
-            #[cfg(all(feature = "postgres", unix))]
+            #[cfg(all(any(feature = "postgres", feature = "mysql"), unix))]
             Inner::UnixStream(_) => false,
             #[cfg(feature = "tls")]
             Inner::Tls(_) => true,
@@ -58,7 +58,7 @@ impl MaybeTlsStream {
     ) -> crate::Result<()> {
         let conn = match std::mem::replace(&mut self.inner, Upgrading) {
             NotTls(conn) => conn,
-            #[cfg(all(feature = "postgres", unix))]
+            #[cfg(all(any(feature = "postgres", feature = "mysql"), unix))]
             UnixStream(_) => {
                 return Err(tls_err!("TLS is not supported with unix domain sockets").into())
             }
@@ -74,7 +74,7 @@ impl MaybeTlsStream {
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         match self.inner {
             NotTls(ref conn) => conn.shutdown(how),
-            #[cfg(all(feature = "postgres", unix))]
+            #[cfg(all(any(feature = "postgres", feature = "mysql"), unix))]
             UnixStream(ref conn) => conn.shutdown(how),
             #[cfg(feature = "tls")]
             Tls(ref conn) => conn.get_ref().shutdown(how),
@@ -89,7 +89,7 @@ macro_rules! forward_pin (
     ($self:ident.$method:ident($($arg:ident),*)) => (
         match &mut $self.inner {
             NotTls(ref mut conn) => Pin::new(conn).$method($($arg),*),
-            #[cfg(all(feature = "postgres", unix))]
+            #[cfg(all(any(feature = "postgres", feature = "mysql"), unix))]
             UnixStream(ref mut conn) => Pin::new(conn).$method($($arg),*),
             #[cfg(feature = "tls")]
             Tls(ref mut conn) => Pin::new(conn).$method($($arg),*),
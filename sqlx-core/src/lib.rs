@@ -37,6 +37,9 @@ pub mod executor;
 pub mod transaction;
 mod url;
 
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
+mod cache;
+
 #[macro_use]
 pub mod arguments;
 pub mod decode;
@@ -47,6 +50,7 @@ pub mod describe;
 pub mod encode;
 pub mod pool;
 pub mod query;
+pub mod statement;
 
 #[macro_use]
 pub mod query_as;
@@ -56,9 +60,7 @@ pub mod types;
 #[macro_use]
 pub mod row;
 
-#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
-#[macro_use]
-mod logging;
+pub mod logging;
 
 #[cfg(feature = "mysql")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mysql")))]
@@ -72,4 +74,11 @@ pub mod postgres;
 #[cfg_attr(docsrs, doc(cfg(feature = "sqlite")))]
 pub mod sqlite;
 
-pub use error::{Error, Result};
+#[cfg(all(
+    feature = "any",
+    any(feature = "postgres", feature = "mysql", feature = "sqlite")
+))]
+#[cfg_attr(docsrs, doc(cfg(feature = "any")))]
+pub mod any;
+
+pub use error::{Error, ErrorKind, Result};
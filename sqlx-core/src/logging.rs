@@ -1,42 +1,300 @@
-use std::time::Duration;
-
-pub(crate) const SLOW_QUERY_THRESHOLD: Duration = Duration::from_secs(1);
-
-/// Logs the query and execution time of a statement as it runs.
-macro_rules! log_execution {
-    ( $query:expr, $block:expr ) => {{
-        // TODO: Log bound parameters
-        let query_string = $query.query_string();
-        let timer = std::time::Instant::now();
-        let result = $block;
-        let elapsed = timer.elapsed();
-        if elapsed >= crate::logging::SLOW_QUERY_THRESHOLD {
-            log::warn!(
-                target: "sqlx::query",
-                "{} ..., elapsed: {:.3?}\n\n{}\n",
-                crate::logging::parse_query_summary(query_string),
-                elapsed,
-                sqlformat::format(
-                    query_string,
-                    &sqlformat::QueryParams::None,
-                    sqlformat::FormatOptions::default()
-                )
-            );
-        } else {
-            log::debug!(
-                target: "sqlx::query",
-                "{} ..., elapsed: {:.3?}\n\n{}\n",
-                crate::logging::parse_query_summary(query_string),
+//! Types for configuring how executed queries are logged and for observing them.
+//!
+//! Each query is logged to the `sqlx::query` target of the [`log`] crate once it finishes
+//! (for a [`Cursor`], once its last row has been read or it is dropped). What is logged, and
+//! at which level, is controlled per connection by [`LogSettings`].
+//!
+//! [`Cursor`]: crate::cursor::Cursor
+
+use std::borrow::Cow;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::LevelFilter;
+
+// Decides whether to redact the bound value at an index of a query
+type RedactArgument = dyn Fn(&str, usize) -> bool + Send + Sync;
+
+/// Settings for the logging of queries executed on a connection.
+///
+/// By default, each query is logged at `DEBUG` and queries taking a second or longer are logged
+/// at `WARN`. Statements are pretty-printed with [`sqlformat`](https://docs.rs/sqlformat) and
+/// bound arguments are not logged.
+///
+/// Settings are applied with [`Connection::set_log_settings`] or to each connection of a pool
+/// with [`Builder::log_settings`].
+///
+/// ```rust,ignore
+/// let settings = LogSettings::new()
+///     .log_slow_statements(LevelFilter::Warn, Duration::from_millis(250))
+///     .format_statements(false)
+///     .log_arguments(true)
+///     .redact_arguments_with(|sql, _index| sql.contains("password"));
+///
+/// conn.set_log_settings(settings);
+/// ```
+///
+/// [`Connection::set_log_settings`]: crate::connection::Connection::set_log_settings
+/// [`Builder::log_settings`]: crate::pool::Builder::log_settings
+#[derive(Clone)]
+pub struct LogSettings {
+    pub(crate) statements_level: LevelFilter,
+    pub(crate) slow_statements_level: LevelFilter,
+    pub(crate) slow_statements_duration: Duration,
+    pub(crate) format_statements: bool,
+    pub(crate) log_arguments: bool,
+    pub(crate) redact_argument: Option<Arc<RedactArgument>>,
+    pub(crate) hook: Option<Arc<dyn QueryHook>>,
+}
+
+impl LogSettings {
+    /// Creates the default settings.
+    pub fn new() -> Self {
+        Self {
+            statements_level: LevelFilter::Debug,
+            slow_statements_level: LevelFilter::Warn,
+            slow_statements_duration: Duration::from_secs(1),
+            format_statements: true,
+            log_arguments: false,
+            redact_argument: None,
+            hook: None,
+        }
+    }
+
+    /// Sets the level at which each query is logged.
+    pub fn log_statements(mut self, level: LevelFilter) -> Self {
+        self.statements_level = level;
+        self
+    }
+
+    /// Sets the level at which queries that take `duration` or longer are logged.
+    pub fn log_slow_statements(mut self, level: LevelFilter, duration: Duration) -> Self {
+        self.slow_statements_level = level;
+        self.slow_statements_duration = duration;
+        self
+    }
+
+    /// Disables logging of queries, including slow queries.
+    ///
+    /// A hook set with [`hook`](LogSettings::hook) is still called.
+    pub fn disable_statement_logging(self) -> Self {
+        self.log_statements(LevelFilter::Off)
+            .log_slow_statements(LevelFilter::Off, Duration::from_secs(1))
+    }
+
+    /// Sets whether logged statements are pretty-printed. Formatting is relatively expensive;
+    /// disabling it logs each statement as it was written.
+    pub fn format_statements(mut self, format: bool) -> Self {
+        self.format_statements = format;
+        self
+    }
+
+    /// Sets whether the values bound to a query are logged (and passed to the hook) along
+    /// with it. Defaults to `false`.
+    pub fn log_arguments(mut self, log: bool) -> Self {
+        self.log_arguments = log;
+        self
+    }
+
+    /// Redacts bound values from the log. The function is called with the query and the
+    /// (zero-based) index of each bound value; if it returns `true`, the value is logged as
+    /// `<redacted>`.
+    pub fn redact_arguments_with<F>(mut self, redact: F) -> Self
+    where
+        F: Fn(&str, usize) -> bool + Send + Sync + 'static,
+    {
+        self.redact_argument = Some(Arc::new(redact));
+        self
+    }
+
+    /// Sets a hook that is called with a [`QueryEvent`] for every query, regardless of the
+    /// log levels.
+    pub fn hook<H>(mut self, hook: H) -> Self
+    where
+        H: QueryHook,
+    {
+        self.hook = Some(Arc::new(hook));
+        self
+    }
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for LogSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LogSettings")
+            .field("statements_level", &self.statements_level)
+            .field("slow_statements_level", &self.slow_statements_level)
+            .field("slow_statements_duration", &self.slow_statements_duration)
+            .field("format_statements", &self.format_statements)
+            .field("log_arguments", &self.log_arguments)
+            .field("redact_arguments", &self.redact_argument.is_some())
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+/// A query that has finished executing, passed to a [`QueryHook`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct QueryEvent<'a> {
+    /// The SQL of the query.
+    pub sql: &'a str,
+
+    /// The values bound to the query, if [`LogSettings::log_arguments`] is enabled, with
+    /// redacted values replaced by `<redacted>`.
+    pub arguments: Option<&'a [String]>,
+
+    /// The time from when the query was sent until its result was consumed.
+    pub elapsed: Duration,
+
+    /// Whether `elapsed` reached the slow query threshold.
+    pub is_slow: bool,
+
+    /// The number of rows returned by the query.
+    pub rows_returned: u64,
+
+    /// The number of rows inserted, updated or deleted by the query, as reported by
+    /// the database.
+    pub rows_affected: u64,
+}
+
+/// A hook that observes every query executed on a connection, e.g. to record metrics.
+///
+/// This is implemented for closures taking a [`QueryEvent`].
+pub trait QueryHook: Send + Sync + 'static {
+    /// Called after a query has finished.
+    fn on_query(&self, event: &QueryEvent<'_>);
+}
+
+impl<F> QueryHook for F
+where
+    F: Fn(&QueryEvent<'_>) + Send + Sync + 'static,
+{
+    fn on_query(&self, event: &QueryEvent<'_>) {
+        self(event)
+    }
+}
+
+/// Tracks a query as it runs and logs it when dropped.
+#[allow(dead_code)]
+pub(crate) struct QueryLogger<'q> {
+    sql: Cow<'q, str>,
+    arguments: Option<Vec<String>>,
+    rows_returned: u64,
+    rows_affected: u64,
+    start: Instant,
+    settings: LogSettings,
+}
+
+#[allow(dead_code)]
+impl<'q> QueryLogger<'q> {
+    pub(crate) fn new(sql: impl Into<Cow<'q, str>>, settings: &LogSettings) -> Self {
+        Self {
+            sql: sql.into(),
+            arguments: None,
+            rows_returned: 0,
+            rows_affected: 0,
+            start: Instant::now(),
+            settings: settings.clone(),
+        }
+    }
+
+    /// Records the values bound to the query. The values are only rendered if the settings
+    /// ask for them to be logged.
+    pub(crate) fn arguments<F>(&mut self, values: F)
+    where
+        F: FnOnce() -> Vec<String>,
+    {
+        if !self.settings.log_arguments {
+            return;
+        }
+
+        let mut values = values();
+
+        if let Some(redact) = &self.settings.redact_argument {
+            for (index, value) in values.iter_mut().enumerate() {
+                if redact(&self.sql, index) {
+                    *value = "<redacted>".to_owned();
+                }
+            }
+        }
+
+        self.arguments = Some(values);
+    }
+
+    pub(crate) fn increment_rows_returned(&mut self) {
+        self.rows_returned += 1;
+    }
+
+    pub(crate) fn add_rows_affected(&mut self, rows: u64) {
+        self.rows_affected += rows;
+    }
+
+    fn finish(&self) {
+        let elapsed = self.start.elapsed();
+        let is_slow = elapsed >= self.settings.slow_statements_duration;
+
+        if let Some(hook) = &self.settings.hook {
+            hook.on_query(&QueryEvent {
+                sql: &self.sql,
+                arguments: self.arguments.as_deref(),
                 elapsed,
-                sqlformat::format(
-                    query_string,
-                    &sqlformat::QueryParams::None,
-                    sqlformat::FormatOptions::default()
-                )
-            );
+                is_slow,
+                rows_returned: self.rows_returned,
+                rows_affected: self.rows_affected,
+            });
         }
-        result
-    }};
+
+        let level = if is_slow {
+            self.settings.slow_statements_level
+        } else {
+            self.settings.statements_level
+        };
+
+        let level = match level.to_level() {
+            Some(level) if log::log_enabled!(target: "sqlx::query", level) => level,
+            _ => return,
+        };
+
+        let sql = if self.settings.format_statements {
+            sqlformat::format(
+                &self.sql,
+                &sqlformat::QueryParams::None,
+                sqlformat::FormatOptions::default(),
+            )
+        } else {
+            self.sql.to_string()
+        };
+
+        let arguments = match &self.arguments {
+            Some(arguments) => format!("\narguments: [{}]\n", arguments.join(", ")),
+            None => String::new(),
+        };
+
+        log::log!(
+            target: "sqlx::query",
+            level,
+            "{} ...; rows returned: {}, rows affected: {}, elapsed: {:.3?}\n\n{}\n{}",
+            parse_query_summary(&self.sql),
+            self.rows_returned,
+            self.rows_affected,
+            elapsed,
+            sql,
+            arguments
+        );
+    }
+}
+
+impl Drop for QueryLogger<'_> {
+    fn drop(&mut self) {
+        self.finish();
+    }
 }
 
 pub(crate) fn parse_query_summary(query: &str) -> String {
@@ -47,3 +305,64 @@ pub(crate) fn parse_query_summary(query: &str) -> String {
         .collect::<Vec<&str>>()
         .join(" ")
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{LogSettings, QueryEvent, QueryLogger};
+
+    #[test]
+    fn it_passes_events_to_the_hook() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+
+        let settings = LogSettings::new()
+            .disable_statement_logging()
+            .log_arguments(true)
+            .redact_arguments_with(|_, index| index == 1)
+            .hook(move |event: &QueryEvent<'_>| {
+                recorded.lock().unwrap().push((
+                    event.sql.to_owned(),
+                    event.arguments.map(|arguments| arguments.to_vec()),
+                    event.rows_returned,
+                    event.rows_affected,
+                ));
+            });
+
+        let mut logger = QueryLogger::new("SELECT ?, ?", &settings);
+
+        logger.arguments(|| vec!["1".to_owned(), "'secret'".to_owned()]);
+        logger.increment_rows_returned();
+        logger.increment_rows_returned();
+        logger.add_rows_affected(3);
+
+        drop(logger);
+
+        let events = events.lock().unwrap();
+
+        assert_eq!(
+            *events,
+            vec![(
+                "SELECT ?, ?".to_owned(),
+                Some(vec!["1".to_owned(), "<redacted>".to_owned()]),
+                2,
+                3
+            )]
+        );
+    }
+
+    #[test]
+    fn it_skips_arguments_unless_enabled() {
+        let mut logger = QueryLogger::new("SELECT ?", &LogSettings::new());
+        let mut rendered = false;
+
+        logger.arguments(|| {
+            rendered = true;
+            vec!["1".to_owned()]
+        });
+
+        assert!(!rendered);
+        assert!(logger.arguments.is_none());
+    }
+}
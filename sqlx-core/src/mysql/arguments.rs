@@ -1,5 +1,9 @@
+use byteorder::{ByteOrder, LittleEndian};
+
 use crate::arguments::Arguments;
 use crate::encode::{Encode, IsNull};
+use crate::mysql::io::BufExt;
+use crate::mysql::protocol::TypeId;
 use crate::mysql::type_info::MySqlTypeInfo;
 use crate::mysql::MySql;
 use crate::types::Type;
@@ -11,6 +15,74 @@ pub struct MySqlArguments {
     pub(crate) null_bitmap: Vec<u8>,
 }
 
+impl MySqlArguments {
+    // Renders each bound value for the query log
+    pub(crate) fn log_values(&self) -> Vec<String> {
+        let mut buf = &*self.params;
+        let mut values = Vec::with_capacity(self.param_types.len());
+
+        for (index, ty) in self.param_types.iter().enumerate() {
+            if self.null_bitmap[index / 8] & (1 << (index % 8)) as u8 != 0 {
+                values.push("NULL".to_owned());
+                continue;
+            }
+
+            match log_value(&mut buf, ty) {
+                Some(value) => values.push(value),
+
+                // the rest of the buffer cannot be interpreted
+                None => break,
+            }
+        }
+
+        values
+    }
+}
+
+fn log_value(buf: &mut &[u8], ty: &MySqlTypeInfo) -> Option<String> {
+    let len = match ty.id {
+        TypeId::TINY_INT => 1,
+        TypeId::SMALL_INT => 2,
+        TypeId::INT | TypeId::FLOAT => 4,
+        TypeId::BIG_INT | TypeId::DOUBLE => 8,
+
+        // temporal values are prefixed with their length
+        TypeId::DATE | TypeId::TIME | TypeId::DATETIME | TypeId::TIMESTAMP => {
+            1 + *buf.first()? as usize
+        }
+
+        _ => {
+            let value = buf.get_bytes_lenenc::<LittleEndian>().ok()??;
+
+            return Some(match std::str::from_utf8(value) {
+                Ok(value) => format!("{:?}", value),
+                Err(_) => format!("x'{}'", hex::encode(value)),
+            });
+        }
+    };
+
+    if buf.len() < len {
+        return None;
+    }
+
+    let (value, rest) = buf.split_at(len);
+    *buf = rest;
+
+    Some(match ty.id {
+        TypeId::TINY_INT if ty.is_unsigned => value[0].to_string(),
+        TypeId::TINY_INT => (value[0] as i8).to_string(),
+        TypeId::SMALL_INT if ty.is_unsigned => LittleEndian::read_u16(value).to_string(),
+        TypeId::SMALL_INT => LittleEndian::read_i16(value).to_string(),
+        TypeId::INT if ty.is_unsigned => LittleEndian::read_u32(value).to_string(),
+        TypeId::INT => LittleEndian::read_i32(value).to_string(),
+        TypeId::BIG_INT if ty.is_unsigned => LittleEndian::read_u64(value).to_string(),
+        TypeId::BIG_INT => LittleEndian::read_i64(value).to_string(),
+        TypeId::FLOAT => LittleEndian::read_f32(value).to_string(),
+        TypeId::DOUBLE => LittleEndian::read_f64(value).to_string(),
+        _ => format!("<{}>", ty),
+    })
+}
+
 impl Arguments for MySqlArguments {
     type Database = MySql;
 
@@ -0,0 +1,62 @@
+use std::fmt::Debug;
+
+use futures_core::future::BoxFuture;
+
+/// The state of an authentication exchange, passed to a [`MySqlAuthPlugin`].
+#[derive(Debug)]
+pub struct MySqlAuthContext<'a> {
+    /// The user being authenticated.
+    pub username: &'a str,
+
+    /// The password from the connection options, if any.
+    pub password: Option<&'a str>,
+
+    /// The authentication data sent by the server along with the request for this plugin
+    /// (for most plugins, this is a random nonce).
+    pub auth_plugin_data: &'a [u8],
+
+    /// Whether the connection to the server is encrypted.
+    pub is_tls: bool,
+}
+
+/// A client-side authentication plugin.
+///
+/// When the server requests an authentication plugin by name (either in the initial handshake
+/// or by switching plugins part way through), a plugin registered with
+/// [`MySqlConnectOptions::auth_plugin`] of the same name is used in place of the built-in
+/// implementation, if any.
+///
+/// This can be used to support authentication mechanisms which SQLx does not implement or to
+/// obtain credentials at connect time, e.g. to fetch a short-lived token.
+///
+/// [`MySqlConnectOptions::auth_plugin`]: crate::mysql::MySqlConnectOptions::auth_plugin
+pub trait MySqlAuthPlugin: Debug + Send + Sync + 'static {
+    /// The name of the plugin, as sent by the server (e.g. `mysql_clear_password`).
+    fn name(&self) -> &str;
+
+    /// Produce the authentication response to send to the server.
+    fn auth_response<'a>(
+        &'a self,
+        context: &'a MySqlAuthContext<'a>,
+    ) -> BoxFuture<'a, crate::Result<Vec<u8>>>;
+
+    /// Handle additional data sent by the server after the initial authentication
+    /// response, producing the packet to send back.
+    ///
+    /// By default, this returns an error.
+    fn continue_auth<'a>(
+        &'a self,
+        context: &'a MySqlAuthContext<'a>,
+        data: &'a [u8],
+    ) -> BoxFuture<'a, crate::Result<Vec<u8>>> {
+        let _ = (context, data);
+
+        Box::pin(async move {
+            Err(protocol_err!(
+                "authentication plugin {:?} received unexpected data from the server",
+                self.name()
+            )
+            .into())
+        })
+    }
+}
@@ -6,18 +6,55 @@ use futures_core::future::BoxFuture;
 use crate::connection::ConnectionSource;
 use crate::cursor::Cursor;
 use crate::executor::Execute;
+use crate::logging::QueryLogger;
 use crate::mysql::protocol::{ColumnCount, ColumnDefinition, Row, Status};
 use crate::mysql::{MySql, MySqlArguments, MySqlConnection, MySqlRow, MySqlTypeInfo};
 use crate::pool::Pool;
+use crate::statement::check_connection;
 
 pub struct MySqlCursor<'c, 'q> {
     source: ConnectionSource<'c, MySqlConnection>,
+    state: MySqlCursorState<'q>,
+}
+
+// The progress of a cursor through the result of its query, kept apart from the connection so
+// that the `Any` driver can read it from a connection it holds itself
+pub(crate) struct MySqlCursorState<'q> {
     query: Option<(&'q str, Option<MySqlArguments>)>,
+    connection_id: Option<u64>,
+    logger: Option<QueryLogger<'q>>,
     column_names: Arc<HashMap<Box<str>, u16>>,
     column_types: Vec<MySqlTypeInfo>,
     binary: bool,
 }
 
+impl<'q> MySqlCursorState<'q> {
+    pub(crate) fn new(
+        query: &'q str,
+        arguments: Option<MySqlArguments>,
+        connection_id: Option<u64>,
+    ) -> Self {
+        Self {
+            query: Some((query, arguments)),
+            connection_id,
+            logger: None,
+            column_names: Arc::default(),
+            column_types: Vec::new(),
+            binary: true,
+        }
+    }
+
+    fn from_query<E>(query: E) -> Self
+    where
+        E: Execute<'q, MySql>,
+    {
+        let connection_id = query.connection_id();
+        let (query, arguments) = query.into_parts();
+
+        Self::new(query, arguments, connection_id)
+    }
+}
+
 impl crate::cursor::private::Sealed for MySqlCursor<'_, '_> {}
 
 impl<'c, 'q> Cursor<'c, 'q> for MySqlCursor<'c, 'q> {
@@ -31,10 +68,7 @@ impl<'c, 'q> Cursor<'c, 'q> for MySqlCursor<'c, 'q> {
     {
         Self {
             source: ConnectionSource::Pool(pool.clone()),
-            column_names: Arc::default(),
-            column_types: Vec::new(),
-            binary: true,
-            query: Some(query.into_parts()),
+            state: MySqlCursorState::from_query(query),
         }
     }
 
@@ -46,10 +80,7 @@ impl<'c, 'q> Cursor<'c, 'q> for MySqlCursor<'c, 'q> {
     {
         Self {
             source: ConnectionSource::ConnectionRef(conn),
-            column_names: Arc::default(),
-            column_types: Vec::new(),
-            binary: true,
-            query: Some(query.into_parts()),
+            state: MySqlCursorState::from_query(query),
         }
     }
 
@@ -61,12 +92,29 @@ impl<'c, 'q> Cursor<'c, 'q> for MySqlCursor<'c, 'q> {
 async fn next<'a, 'c: 'a, 'q: 'a>(
     cursor: &'a mut MySqlCursor<'c, 'q>,
 ) -> crate::Result<Option<MySqlRow<'a>>> {
-    let mut conn = cursor.source.resolve().await?;
+    let conn = cursor.source.resolve().await?;
+
+    next_row(conn, &mut cursor.state).await
+}
 
+pub(crate) async fn next_row<'a, 'q: 'a>(
+    conn: &'a mut MySqlConnection,
+    cursor: &'a mut MySqlCursorState<'q>,
+) -> crate::Result<Option<MySqlRow<'a>>> {
     // The first time [next] is called we need to actually execute our
     // contained query. We guard against this happening on _all_ next calls
     // by using [Option::take] which replaces the potential value in the Option with `None
     let mut initial = if let Some((query, arguments)) = cursor.query.take() {
+        check_connection(cursor.connection_id, conn.id)?;
+
+        let mut logger = QueryLogger::new(query, &conn.log_settings);
+
+        if let Some(arguments) = &arguments {
+            logger.arguments(|| arguments.log_values());
+        }
+
+        cursor.logger = Some(logger);
+
         let statement = conn.run(query, arguments).await?;
 
         // No statement ID = TEXT mode
@@ -88,7 +136,13 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
                 let status = if let Some(eof) = conn.stream.maybe_handle_eof()? {
                     eof.status
                 } else {
-                    conn.stream.handle_ok()?.status
+                    let ok = conn.stream.handle_ok()?;
+
+                    if let Some(logger) = &mut cursor.logger {
+                        logger.add_rows_affected(ok.affected_rows);
+                    }
+
+                    ok.status
                 };
 
                 if status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
@@ -96,6 +150,10 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
                     initial = true;
                 } else {
                     conn.is_ready = true;
+
+                    // The query is logged once its result has been consumed
+                    cursor.logger = None;
+
                     return Ok(None);
                 }
             }
@@ -153,6 +211,10 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
                     names: Arc::clone(&cursor.column_names),
                 };
 
+                if let Some(logger) = &mut cursor.logger {
+                    logger.increment_rows_returned();
+                }
+
                 return Ok(Some(row));
             }
 
@@ -1,7 +1,7 @@
 use std::error::Error as StdError;
 use std::fmt::{self, Display};
 
-use crate::error::DatabaseError;
+use crate::error::{DatabaseError, ErrorKind};
 use crate::mysql::protocol::ErrPacket;
 
 #[derive(Debug)]
@@ -22,6 +22,32 @@ impl DatabaseError for MySqlError {
         self.0.sql_state.as_deref()
     }
 
+    fn kind(&self) -> ErrorKind {
+        // https://dev.mysql.com/doc/mysql-errors/8.0/en/server-error-reference.html
+        match self.0.error_code {
+            // ER_DUP_ENTRY, ER_DUP_UNIQUE, ER_DUP_ENTRY_WITH_KEY_NAME
+            1062 | 1169 | 1586 => ErrorKind::UniqueViolation,
+
+            // ER_NO_REFERENCED_ROW, ER_ROW_IS_REFERENCED, ER_ROW_IS_REFERENCED_2,
+            // ER_NO_REFERENCED_ROW_2
+            1216 | 1217 | 1451 | 1452 => ErrorKind::ForeignKeyViolation,
+
+            // ER_BAD_NULL_ERROR
+            1048 => ErrorKind::NotNullViolation,
+
+            // ER_CHECK_CONSTRAINT_VIOLATED
+            3819 => ErrorKind::CheckViolation,
+
+            // ER_LOCK_DEADLOCK
+            1213 => ErrorKind::Deadlock,
+
+            // ER_LOCK_WAIT_TIMEOUT
+            1205 => ErrorKind::LockTimeout,
+
+            _ => ErrorKind::Other,
+        }
+    }
+
     fn as_ref_err(&self) -> &(dyn StdError + Send + Sync + 'static) {
         self
     }
@@ -58,6 +84,23 @@ fn test_error_downcasting() {
         e => panic!("expected crate::Error::Database, got {:?}", e),
     };
 
+    assert_eq!(db_err.kind(), ErrorKind::Other);
     assert_eq!(db_err.downcast_ref::<MySqlError>().0.error_code, 0xABCD);
     assert_eq!(db_err.downcast::<MySqlError>().0.error_code, 0xABCD);
 }
+
+#[test]
+fn test_error_kind() {
+    let error = |error_code| {
+        MySqlError(ErrPacket {
+            error_code,
+            sql_state: None,
+            error_message: "".into(),
+        })
+    };
+
+    assert_eq!(error(1062).kind(), ErrorKind::UniqueViolation);
+    assert_eq!(error(1452).kind(), ErrorKind::ForeignKeyViolation);
+    assert_eq!(error(1213).kind(), ErrorKind::Deadlock);
+    assert_eq!(error(1205).kind(), ErrorKind::LockTimeout);
+}
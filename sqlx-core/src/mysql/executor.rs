@@ -2,16 +2,18 @@ use futures_core::future::BoxFuture;
 
 use crate::cursor::Cursor;
 use crate::describe::{Column, Describe};
-use crate::executor::{Execute, Executor, RefExecutor};
+use crate::executor::{Execute, Executor, QueryResult, RefExecutor, StatementResult};
+use crate::logging::QueryLogger;
 use crate::mysql::protocol::{
-    self, ColumnDefinition, ComQuery, ComStmtExecute, ComStmtPrepare, ComStmtPrepareOk, FieldFlags,
-    Status,
+    self, ColumnDefinition, ComQuery, ComStmtClose, ComStmtExecute, ComStmtPrepare,
+    ComStmtPrepareOk, FieldFlags, Status,
 };
 use crate::mysql::{MySql, MySqlArguments, MySqlCursor, MySqlTypeInfo};
+use crate::statement::{check_connection, Statement};
 
 impl super::MySqlConnection {
     // Creates a prepared statement for the passed query string
-    async fn prepare(&mut self, query: &str) -> crate::Result<ComStmtPrepareOk> {
+    async fn send_prepare(&mut self, query: &str) -> crate::Result<ComStmtPrepareOk> {
         // https://dev.mysql.com/doc/dev/mysql-server/8.0.11/page_protocol_com_stmt_prepare.html
         self.stream.send(ComStmtPrepare { query }, true).await?;
 
@@ -40,12 +42,10 @@ impl super::MySqlConnection {
     // Gets a cached prepared statement ID _or_ prepares the statement if not in the cache
     // At the end we should have [cache_statement] and [cache_statement_columns] filled
     async fn get_or_prepare(&mut self, query: &str) -> crate::Result<u32> {
-        if let Some(&id) = self.cache_statement.get(query) {
+        if let Some(&mut id) = self.cache_statement.get_mut(query) {
             Ok(id)
         } else {
-            let stmt = self.prepare(query).await?;
-
-            self.cache_statement.insert(query.into(), stmt.statement_id);
+            let stmt = self.send_prepare(query).await?;
 
             // COM_STMT_PREPARE returns the input columns
             // We make no use of that data, so cycle through and drop them
@@ -55,10 +55,31 @@ impl super::MySqlConnection {
             // We just drop these as we get these when we execute the query
             self.drop_column_defs(stmt.columns as usize).await?;
 
+            // Statements pushed out of the cache are closed on the server
+            for statement_id in self.cache_statement.insert(query, stmt.statement_id) {
+                self.close_statement(statement_id).await?;
+            }
+
             Ok(stmt.statement_id)
         }
     }
 
+    async fn close_statement(&mut self, statement_id: u32) -> crate::Result<()> {
+        // https://dev.mysql.com/doc/dev/mysql-server/8.0.11/page_protocol_com_stmt_close.html
+        // The server does not send a response to COM_STMT_CLOSE
+        self.stream.send(ComStmtClose { statement_id }, true).await
+    }
+
+    pub(crate) async fn clear_statement_cache(&mut self) -> crate::Result<()> {
+        self.stream.wait_until_ready().await?;
+
+        for statement_id in self.cache_statement.clear() {
+            self.close_statement(statement_id).await?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) async fn run(
         &mut self,
         query: &str,
@@ -93,8 +114,9 @@ impl super::MySqlConnection {
         }
     }
 
-    async fn affected_rows(&mut self) -> crate::Result<u64> {
-        let mut rows = 0;
+    // Reads the OK packet of each statement of the query
+    async fn affected_rows(&mut self) -> crate::Result<QueryResult> {
+        let mut result = QueryResult::default();
 
         loop {
             let id = self.stream.receive().await?[0];
@@ -109,7 +131,13 @@ impl super::MySqlConnection {
                     } else {
                         let ok = self.stream.handle_ok()?;
 
-                        rows += ok.affected_rows;
+                        // An ID of 0 means that no row was inserted
+                        result.push(StatementResult {
+                            rows_affected: ok.affected_rows,
+                            last_insert_id: Some(ok.last_insert_id as i64).filter(|id| *id != 0),
+                            command: None,
+                        });
+
                         ok.status
                     };
 
@@ -127,7 +155,7 @@ impl super::MySqlConnection {
             }
         }
 
-        Ok(rows)
+        Ok(result)
     }
 
     // method is not named describe to work around an intellijrust bug
@@ -135,8 +163,34 @@ impl super::MySqlConnection {
     async fn do_describe(&mut self, query: &str) -> crate::Result<Describe<MySql>> {
         self.stream.wait_until_ready().await?;
 
-        let stmt = self.prepare(query).await?;
+        let stmt = self.send_prepare(query).await?;
+        let describe = self.read_describe(&stmt).await?;
+
+        // The statement was only prepared to describe it
+        self.close_statement(stmt.statement_id).await?;
+
+        Ok(describe)
+    }
+
+    pub(crate) async fn prepare_statement(
+        &mut self,
+        query: &str,
+    ) -> crate::Result<Statement<MySql>> {
+        self.stream.wait_until_ready().await?;
+
+        let stmt = self.send_prepare(query).await?;
+        let describe = self.read_describe(&stmt).await?;
+
+        // The statement is cached so that executing it does not prepare it again
+        for statement_id in self.cache_statement.insert(query, stmt.statement_id) {
+            self.close_statement(statement_id).await?;
+        }
 
+        Ok(Statement::new(query, self.id, describe))
+    }
+
+    // Reads the parameter and result column definitions that follow COM_STMT_PREPARE_OK
+    async fn read_describe(&mut self, stmt: &ComStmtPrepareOk) -> crate::Result<Describe<MySql>> {
         let mut param_types = Vec::with_capacity(stmt.params as usize);
         let mut result_columns = Vec::with_capacity(stmt.columns as usize);
 
@@ -178,17 +232,26 @@ impl Executor for super::MySqlConnection {
     fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
         &'c mut self,
         query: E,
-    ) -> BoxFuture<'e, crate::Result<u64>>
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, {
-            Box::pin(async move {
-                let (query, arguments) = query.into_parts();
+        Box::pin(async move {
+            check_connection(query.connection_id(), self.id)?;
+
+            let (query, arguments) = query.into_parts();
+            let mut logger = QueryLogger::new(query, &self.log_settings);
+
+            if let Some(arguments) = &arguments {
+                logger.arguments(|| arguments.log_values());
+            }
+
+            self.run(query, arguments).await?;
+
+            let result = self.affected_rows().await?;
+            logger.add_rows_affected(result.rows_affected());
 
-                self.run(query, arguments).await?;
-                self.affected_rows().await
-            })
+            Ok(result)
         })
     }
 
@@ -196,7 +259,7 @@ impl Executor for super::MySqlConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { MySqlCursor::from_connection(self, query) })
+        MySqlCursor::from_connection(self, query)
     }
 
     #[doc(hidden)]
@@ -218,6 +281,6 @@ impl<'c> RefExecutor<'c> for &'c mut super::MySqlConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { MySqlCursor::from_connection(self, query) })
+        MySqlCursor::from_connection(self, query)
     }
 }
@@ -1,21 +1,25 @@
 //! **MySQL** database and connection types.
 
 pub use arguments::MySqlArguments;
+pub use auth::{MySqlAuthContext, MySqlAuthPlugin};
 pub use connection::MySqlConnection;
 pub use cursor::MySqlCursor;
 pub use database::MySql;
 pub use error::MySqlError;
+pub use options::{MySqlConnectOptions, MySqlSslMode};
 pub use row::MySqlRow;
 pub use type_info::MySqlTypeInfo;
 pub use value::{MySqlData, MySqlValue};
 
 mod arguments;
+mod auth;
 mod connection;
-mod cursor;
+pub(crate) mod cursor;
 mod database;
 mod error;
 mod executor;
 mod io;
+mod options;
 mod protocol;
 mod row;
 mod rsa;
@@ -11,26 +11,38 @@ pub enum AuthPlugin {
     MySqlNativePassword,
     CachingSha2Password,
     Sha256Password,
+    MySqlClearPassword,
+
+    // Authenticates using the credentials of the OS user on the other end of a unix socket
+    // MySQL calls this `auth_socket` and MariaDB calls this `unix_socket`
+    AuthSocket(&'static str),
+
+    // Any other plugin; this can only be used by registering a [MySqlAuthPlugin]
+    Other(Box<str>),
 }
 
 impl AuthPlugin {
-    pub(crate) fn from_opt_str(s: Option<&str>) -> crate::Result<AuthPlugin> {
+    pub(crate) fn from_opt_str(s: Option<&str>) -> AuthPlugin {
         match s {
-            Some("mysql_native_password") | None => Ok(AuthPlugin::MySqlNativePassword),
-            Some("caching_sha2_password") => Ok(AuthPlugin::CachingSha2Password),
-            Some("sha256_password") => Ok(AuthPlugin::Sha256Password),
-
-            Some(s) => {
-                Err(protocol_err!("requires unimplemented authentication plugin: {}", s).into())
-            }
+            Some("mysql_native_password") | None => AuthPlugin::MySqlNativePassword,
+            Some("caching_sha2_password") => AuthPlugin::CachingSha2Password,
+            Some("sha256_password") => AuthPlugin::Sha256Password,
+            Some("mysql_clear_password") => AuthPlugin::MySqlClearPassword,
+            Some("auth_socket") => AuthPlugin::AuthSocket("auth_socket"),
+            Some("unix_socket") => AuthPlugin::AuthSocket("unix_socket"),
+
+            Some(s) => AuthPlugin::Other(s.into()),
         }
     }
 
-    pub(crate) fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &str {
         match self {
             AuthPlugin::MySqlNativePassword => "mysql_native_password",
             AuthPlugin::CachingSha2Password => "caching_sha2_password",
             AuthPlugin::Sha256Password => "sha256_password",
+            AuthPlugin::MySqlClearPassword => "mysql_clear_password",
+            AuthPlugin::AuthSocket(name) => name,
+            AuthPlugin::Other(name) => name,
         }
     }
 
@@ -21,7 +21,7 @@ impl AuthSwitch {
             ))?;
         }
 
-        let auth_plugin = AuthPlugin::from_opt_str(Some(buf.get_str_nul()?))?;
+        let auth_plugin = AuthPlugin::from_opt_str(Some(buf.get_str_nul()?));
         let auth_plugin_data = buf.get_bytes(buf.len())?.to_owned().into_boxed_slice();
 
         Ok(Self {
@@ -82,9 +82,9 @@ impl Handshake {
         }
 
         let auth_plugin = if capabilities.contains(Capabilities::PLUGIN_AUTH) {
-            AuthPlugin::from_opt_str(Some(buf.get_str_nul()?))?
+            AuthPlugin::from_opt_str(Some(buf.get_str_nul()?))
         } else {
-            AuthPlugin::from_opt_str(None)?
+            AuthPlugin::from_opt_str(None)
         };
 
         Ok(Self {
@@ -14,6 +14,7 @@ pub struct HandshakeResponse<'a> {
     pub database: Option<&'a str>,
     pub auth_plugin: &'a AuthPlugin,
     pub auth_response: &'a [u8],
+    pub connect_attrs: &'a [(String, String)],
 }
 
 impl Encode for HandshakeResponse<'_> {
@@ -68,5 +69,18 @@ impl Encode for HandshakeResponse<'_> {
             // client_plugin_name : string<NUL>
             buf.put_str_nul(self.auth_plugin.as_str());
         }
+
+        if capabilities.contains(Capabilities::CONNECT_ATTRS) {
+            let mut attrs = Vec::new();
+
+            for (key, value) in self.connect_attrs {
+                attrs.put_str_lenenc::<LittleEndian>(key);
+                attrs.put_str_lenenc::<LittleEndian>(value);
+            }
+
+            // length of all key-values : int<lenenc>
+            // key-values : string<lenenc>[]
+            buf.put_bytes_lenenc::<LittleEndian>(&attrs);
+        }
     }
 }
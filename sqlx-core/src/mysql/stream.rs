@@ -1,12 +1,13 @@
+#[cfg(not(unix))]
+use std::io;
 use std::net::Shutdown;
 
 use byteorder::{ByteOrder, LittleEndian};
 
 use crate::io::{Buf, BufMut, BufStream, MaybeTlsStream};
-use crate::mysql::protocol::{Capabilities, Encode, EofPacket, ErrPacket, OkPacket};
+use crate::mysql::protocol::{Capabilities, Encode, EofPacket, ErrPacket, OkPacket, Status};
 
-use crate::mysql::MySqlError;
-use crate::url::Url;
+use crate::mysql::{MySqlConnectOptions, MySqlError};
 
 // Size before a packet is split
 const MAX_PACKET_SIZE: u32 = 1024;
@@ -25,6 +26,9 @@ pub(crate) struct MySqlStream {
     // This number must be 0 at the start of each command
     pub(super) seq_no: u8,
 
+    // Server status reported by the last OK or EOF packet
+    pub(super) status: Status,
+
     // Packets are buffered into a second buffer from the stream
     // as we may have compressed or split packets to figure out before
     // decoding
@@ -33,10 +37,22 @@ pub(crate) struct MySqlStream {
 }
 
 impl MySqlStream {
-    pub(super) async fn new(url: &Url) -> crate::Result<Self> {
-        let host = url.host().unwrap_or("localhost");
-        let port = url.port(3306);
-        let stream = MaybeTlsStream::connect(host, port).await?;
+    pub(super) async fn new(options: &MySqlConnectOptions) -> crate::Result<Self> {
+        let stream = match &options.socket {
+            #[cfg(unix)]
+            Some(socket) => MaybeTlsStream::connect_uds(socket).await?,
+
+            #[cfg(not(unix))]
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "unix domain sockets are not supported on this platform",
+                )
+                .into());
+            }
+
+            None => MaybeTlsStream::connect(&options.host, options.port).await?,
+        };
 
         let mut capabilities = Capabilities::PROTOCOL_41
             | Capabilities::IGNORE_SPACE
@@ -47,13 +63,15 @@ impl MySqlStream {
             | Capabilities::PLUGIN_AUTH_LENENC_DATA
             | Capabilities::MULTI_STATEMENTS
             | Capabilities::MULTI_RESULTS
-            | Capabilities::PLUGIN_AUTH;
+            | Capabilities::PLUGIN_AUTH
+            | Capabilities::CONNECT_ATTRS;
 
-        if url.database().is_some() {
+        if options.database.is_some() {
             capabilities |= Capabilities::CONNECT_WITH_DB;
         }
 
-        if cfg!(feature = "tls") {
+        // TLS is not used over a unix domain socket
+        if cfg!(feature = "tls") && options.socket.is_none() {
             capabilities |= Capabilities::SSL;
         }
 
@@ -63,6 +81,7 @@ impl MySqlStream {
             packet_buf: Vec::with_capacity(MAX_PACKET_SIZE as usize),
             packet_len: 0,
             seq_no: 0,
+            status: Status::empty(),
             is_ready: true,
         })
     }
@@ -179,7 +198,10 @@ impl MySqlStream {
 
     pub(crate) fn maybe_handle_eof(&mut self) -> crate::Result<Option<EofPacket>> {
         if !self.capabilities.contains(Capabilities::DEPRECATE_EOF) && self.packet()[0] == 0xFE {
-            Ok(Some(EofPacket::read(self.packet())?))
+            let eof = EofPacket::read(self.packet())?;
+            self.status = eof.status;
+
+            Ok(Some(eof))
         } else {
             Ok(None)
         }
@@ -196,7 +218,11 @@ impl MySqlStream {
 
     pub(crate) fn handle_ok(&mut self) -> crate::Result<OkPacket> {
         self.is_ready = true;
-        OkPacket::read(self.packet())
+
+        let ok = OkPacket::read(self.packet())?;
+        self.status = ok.status;
+
+        Ok(ok)
     }
 
     pub(crate) async fn wait_until_ready(&mut self) -> crate::Result<()> {
@@ -206,6 +232,10 @@ impl MySqlStream {
                 match packet_id {
                     0xFE if self.packet().len() < 0xFF_FF_FF => {
                         // OK or EOF packet
+                        if self.maybe_handle_eof()?.is_none() {
+                            self.handle_ok()?;
+                        }
+
                         self.is_ready = true;
                         break;
                     }
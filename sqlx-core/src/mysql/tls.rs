@@ -1,83 +1,74 @@
 use crate::mysql::stream::MySqlStream;
-use crate::url::Url;
+use crate::mysql::{MySqlConnectOptions, MySqlSslMode};
 
 #[cfg_attr(not(feature = "tls"), allow(unused_variables))]
-pub(super) async fn upgrade_if_needed(stream: &mut MySqlStream, url: &Url) -> crate::Result<()> {
+pub(super) async fn upgrade_if_needed(
+    stream: &mut MySqlStream,
+    options: &MySqlConnectOptions,
+) -> crate::Result<()> {
     #[cfg_attr(not(feature = "tls"), allow(unused_imports))]
     use crate::mysql::protocol::Capabilities;
 
-    let ca_file = url.param("ssl-ca");
-    let ssl_mode = url.param("ssl-mode");
+    let ca_file = options.ssl_ca.as_deref();
 
     // https://dev.mysql.com/doc/refman/5.7/en/connection-options.html#option_general_ssl-mode
-    match ssl_mode.as_deref() {
-        Some("DISABLED") => {}
+    match options.ssl_mode {
+        MySqlSslMode::Disabled => {}
 
         #[cfg(feature = "tls")]
-        Some("PREFERRED") | None if !stream.capabilities.contains(Capabilities::SSL) => {}
+        MySqlSslMode::Preferred if !stream.capabilities.contains(Capabilities::SSL) => {}
 
         #[cfg(feature = "tls")]
-        Some("PREFERRED") => {
-            if let Err(_error) = try_upgrade(stream, &url, None, true).await {
+        MySqlSslMode::Preferred => {
+            if let Err(_error) = try_upgrade(stream, options, ca_file, true).await {
                 // TLS upgrade failed; fall back to a normal connection
             }
         }
 
         #[cfg(feature = "tls")]
-        None => {
-            if let Err(_error) = try_upgrade(stream, &url, ca_file.as_deref(), true).await {
-                // TLS upgrade failed; fall back to a normal connection
-            }
-        }
-
-        #[cfg(feature = "tls")]
-        Some("REQUIRED") | Some("VERIFY_CA") | Some("VERIFY_IDENTITY")
+        MySqlSslMode::Required | MySqlSslMode::VerifyCa | MySqlSslMode::VerifyIdentity
             if !stream.capabilities.contains(Capabilities::SSL) =>
         {
             return Err(tls_err!("server does not support TLS").into());
         }
 
         #[cfg(feature = "tls")]
-        Some("VERIFY_CA") | Some("VERIFY_IDENTITY") if ca_file.is_none() => {
-            return Err(
-                tls_err!("`ssl-mode` of {:?} requires `ssl-ca` to be set", ssl_mode).into(),
-            );
+        mode @ MySqlSslMode::VerifyCa | mode @ MySqlSslMode::VerifyIdentity
+            if ca_file.is_none() =>
+        {
+            return Err(tls_err!("`ssl-mode` of {:?} requires `ssl-ca` to be set", mode).into());
         }
 
         #[cfg(feature = "tls")]
-        Some(mode @ "REQUIRED") | Some(mode @ "VERIFY_CA") | Some(mode @ "VERIFY_IDENTITY") => {
+        mode @ MySqlSslMode::Required
+        | mode @ MySqlSslMode::VerifyCa
+        | mode @ MySqlSslMode::VerifyIdentity => {
             try_upgrade(
                 stream,
-                url,
+                options,
                 // false for both verify-ca and verify-full
-                ca_file.as_deref(),
+                ca_file,
                 // false for only verify-full
-                mode != "VERIFY_IDENTITY",
+                mode != MySqlSslMode::VerifyIdentity,
             )
             .await?;
         }
 
         #[cfg(not(feature = "tls"))]
-        None => {
-            // The user neither explicitly enabled TLS in the connection string
-            // nor did they turn the `tls` feature on
+        MySqlSslMode::Preferred => {
+            // The user did not turn the `tls` feature on
         }
 
         #[cfg(not(feature = "tls"))]
-        Some(mode @ "PREFERRED")
-        | Some(mode @ "REQUIRED")
-        | Some(mode @ "VERIFY_CA")
-        | Some(mode @ "VERIFY_IDENTITY") => {
+        mode @ MySqlSslMode::Required
+        | mode @ MySqlSslMode::VerifyCa
+        | mode @ MySqlSslMode::VerifyIdentity => {
             return Err(tls_err!(
                 "ssl-mode {:?} unsupported; SQLx was compiled without `tls` feature",
                 mode
             )
             .into());
         }
-
-        Some(mode) => {
-            return Err(tls_err!("unknown `ssl-mode` value: {:?}", mode).into());
-        }
     }
 
     Ok(())
@@ -86,8 +77,8 @@ pub(super) async fn upgrade_if_needed(stream: &mut MySqlStream, url: &Url) -> cr
 #[cfg(feature = "tls")]
 async fn try_upgrade(
     stream: &mut MySqlStream,
-    url: &Url,
-    ca_file: Option<&str>,
+    options: &MySqlConnectOptions,
+    ca_file: Option<&std::path::Path>,
     accept_invalid_hostnames: bool,
 ) -> crate::Result<()> {
     use crate::mysql::protocol::SslRequest;
@@ -116,8 +107,5 @@ async fn try_upgrade(
         )
         .await?;
 
-    stream
-        .stream
-        .upgrade(url.host().unwrap_or("localhost"), connector)
-        .await
+    stream.stream.upgrade(&options.host, connector).await
 }
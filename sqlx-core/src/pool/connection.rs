@@ -5,7 +5,12 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use super::inner::{DecrementSizeGuard, SharedPool};
+use super::metrics::CloseReason;
 use crate::connection::{Connect, Connection};
+use crate::logging::LogSettings;
+use crate::runtime::spawn;
+use crate::statement::Statement;
+use crate::transaction::TransactionOptions;
 
 /// A connection checked out from [`Pool`][crate::pool::Pool].
 ///
@@ -16,6 +21,7 @@ where
 {
     live: Option<Live<C>>,
     pub(crate) pool: Arc<SharedPool<C>>,
+    checked_out: Instant,
 }
 
 pub(super) struct Live<C> {
@@ -81,7 +87,12 @@ where
     fn close(mut self) -> BoxFuture<'static, crate::Result<()>> {
         Box::pin(async move {
             let live = self.live.take().expect("PoolConnection double-dropped");
-            live.float(&self.pool).into_idle().close().await
+            let result = live.float(&self.pool).into_idle().close().await;
+
+            self.pool.record_release(self.checked_out.elapsed());
+            self.pool.record_close(CloseReason::Closed);
+
+            result
         })
     }
 
@@ -89,22 +100,150 @@ where
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         Box::pin(self.deref_mut().ping())
     }
+
+    #[inline]
+    fn cached_statements_size(&self) -> usize {
+        self.deref().cached_statements_size()
+    }
+
+    #[inline]
+    fn set_statement_cache_capacity(&mut self, capacity: usize) {
+        self.deref_mut().set_statement_cache_capacity(capacity)
+    }
+
+    #[inline]
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>> {
+        self.deref_mut().clear_cached_statements()
+    }
+
+    #[inline]
+    fn log_settings(&self) -> &LogSettings {
+        self.deref().log_settings()
+    }
+
+    #[inline]
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        self.deref_mut().set_log_settings(settings)
+    }
+
+    #[inline]
+    fn prepare<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, crate::Result<Statement<Self::Database>>> {
+        self.deref_mut().prepare(sql)
+    }
+
+    #[inline]
+    fn is_broken(&self) -> bool {
+        self.deref().is_broken()
+    }
+
+    #[inline]
+    fn in_transaction(&self) -> bool {
+        self.deref().in_transaction()
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    fn begin_statements(&self, options: &TransactionOptions) -> Vec<String> {
+        self.deref().begin_statements(options)
+    }
 }
 
 /// Returns the connection to the [`Pool`][crate::pool::Pool] it was checked-out from.
+///
+/// A [broken](Connection::is_broken) connection is closed instead, as is a connection the pool
+/// no longer has room for or that is older than [`close_older_than`] allows. If a transaction
+/// is still open on the connection, it is rolled back in the background before the connection
+/// is returned, followed by the [`after_release`] hook of the pool; if either fails, the
+/// connection is closed instead.
+///
+/// [`after_release`]: crate::pool::Builder::after_release
+/// [`close_older_than`]: crate::pool::Pool::close_older_than
 impl<C> Drop for PoolConnection<C>
 where
     C: Connect,
 {
     fn drop(&mut self) {
         if let Some(live) = self.live.take() {
-            self.pool.release(live.float(&self.pool));
+            self.pool.record_release(self.checked_out.elapsed());
+
+            if live.raw.is_broken() {
+                // The connection is dropped without a goodbye, as it cannot be sent
+                drop(live.float(&self.pool));
+                self.pool.record_close(CloseReason::Broken);
+            } else if self.pool.should_retire(&live) {
+                self.pool.retire(live);
+            } else if self.pool.give_up_size() {
+                self.pool.retire_excess(live);
+            } else if live.raw.in_transaction() || self.pool.options().after_release.is_some() {
+                spawn(reset_and_release(Arc::clone(&self.pool), live));
+            } else {
+                self.pool.release(live.float(&self.pool));
+            }
         }
     }
 }
 
+// The connection keeps its place in the pool's size until it is released or closed, so it
+// cannot be handed to another acquirer while it is being reset
+async fn reset_and_release<C>(pool: Arc<SharedPool<C>>, live: Live<C>)
+where
+    C: Connect,
+{
+    let mut floating = live.float(&pool);
+
+    if floating.raw.in_transaction() {
+        let rolled_back = floating.raw.execute("ROLLBACK").await.is_ok();
+
+        if !rolled_back || floating.raw.in_transaction() {
+            let _ = floating.into_idle().close().await;
+            pool.record_close(CloseReason::Error);
+            return;
+        }
+    }
+
+    if let Some(after_release) = &pool.options().after_release {
+        match after_release(&mut floating.raw).await {
+            Ok(true) => {}
+
+            Ok(false) => {
+                let _ = floating.into_idle().close().await;
+                pool.record_close(CloseReason::Rejected);
+                return;
+            }
+
+            Err(e) => {
+                log::info!("after_release hook returned error: {}", e);
+                let _ = floating.into_idle().close().await;
+                pool.record_close(CloseReason::Error);
+                return;
+            }
+        }
+    }
+
+    // The pool may have been closed or shrunk in the meantime
+    if pool.should_retire(&floating) {
+        let _ = floating.into_idle().close().await;
+        pool.record_close(CloseReason::Closed);
+        return;
+    }
+
+    if pool.give_up_size() {
+        let _ = floating.into_leakable().into_raw().close().await;
+        pool.record_close(CloseReason::Closed);
+        return;
+    }
+
+    pool.release(floating);
+}
+
 impl<C> Live<C> {
-    pub fn float(self, pool: &SharedPool<C>) -> Floating<Self> {
+    pub fn float(self, pool: &SharedPool<C>) -> Floating<Self>
+    where
+        C: Connect,
+    {
         Floating {
             inner: self,
             guard: DecrementSizeGuard::new(pool),
@@ -117,6 +256,16 @@ impl<C> Live<C> {
             since: Instant::now(),
         }
     }
+
+    pub fn into_raw(self) -> C {
+        self.raw
+    }
+}
+
+impl<C> Idle<C> {
+    pub fn into_live(self) -> Live<C> {
+        self.live
+    }
 }
 
 impl<C> Deref for Idle<C> {
@@ -166,6 +315,7 @@ impl<'s, C> Floating<'s, Live<C>> {
         PoolConnection {
             live: Some(inner),
             pool: Arc::clone(pool),
+            checked_out: Instant::now(),
         }
     }
 
@@ -178,7 +328,10 @@ impl<'s, C> Floating<'s, Live<C>> {
 }
 
 impl<'s, C> Floating<'s, Idle<C>> {
-    pub fn from_idle(idle: Idle<C>, pool: &'s SharedPool<C>) -> Self {
+    pub fn from_idle(idle: Idle<C>, pool: &'s SharedPool<C>) -> Self
+    where
+        C: Connect,
+    {
         Self {
             inner: idle,
             guard: DecrementSizeGuard::new(pool),
@@ -192,6 +345,10 @@ impl<'s, C> Floating<'s, Idle<C>> {
         self.live.raw.ping().await
     }
 
+    pub fn raw_mut(&mut self) -> &mut C {
+        &mut self.live.raw
+    }
+
     pub fn into_live(self) -> Floating<'s, Live<C>> {
         Floating {
             inner: self.inner.live,
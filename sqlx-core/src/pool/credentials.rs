@@ -0,0 +1,40 @@
+use std::fmt;
+
+/// A username and password supplied by the credential provider of a pool, replacing those of
+/// the connect options for each new connection.
+///
+/// See [`Builder::credentials`](crate::pool::Builder::credentials).
+#[derive(Clone, Default)]
+pub struct Credentials {
+    pub(crate) username: Option<String>,
+    pub(crate) password: Option<String>,
+}
+
+impl Credentials {
+    /// Creates empty credentials, which keep the username and password of the connect options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the username to connect as.
+    pub fn username(mut self, username: &str) -> Self {
+        self.username = Some(username.to_owned());
+        self
+    }
+
+    /// Sets the password to connect with, e.g. a short-lived authentication token.
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_owned());
+        self
+    }
+}
+
+// The password is left out, so that it does not end up in logs
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
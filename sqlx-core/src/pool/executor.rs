@@ -6,7 +6,7 @@ use crate::cursor::{Cursor, HasCursor};
 use crate::database::Database;
 use crate::describe::Describe;
 use crate::executor::Execute;
-use crate::executor::{Executor, RefExecutor};
+use crate::executor::{Executor, QueryResult, RefExecutor};
 use crate::pool::Pool;
 
 impl<'p, C, DB> Executor for &'p Pool<C>
@@ -20,7 +20,7 @@ where
     fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
         &'c mut self,
         query: E,
-    ) -> BoxFuture<'e, crate::Result<u64>>
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
     where
         E: Execute<'q, Self::Database>,
     {
@@ -72,7 +72,7 @@ where
     fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
         &'c mut self,
         query: E,
-    ) -> BoxFuture<'e, crate::Result<u64>>
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
     where
         E: Execute<'q, Self::Database>,
     {
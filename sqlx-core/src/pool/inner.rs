@@ -2,48 +2,71 @@ use std::cmp;
 use std::mem;
 use std::ptr;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use crossbeam_queue::{ArrayQueue, SegQueue};
-use futures_core::task::{Poll, Waker};
+use crossbeam_queue::SegQueue;
+use futures_core::task::Poll;
 use futures_util::future;
 
 use crate::pool::deadline_as_timeout;
 use crate::runtime::{sleep, spawn, timeout};
-use crate::{
-    connection::{Connect, Connection},
-    error::Error,
-};
+use crate::{connection::Connect, error::Error};
 
 use super::connection::{Floating, Idle, Live};
+use super::metrics::{CloseReason, Metrics, PoolMetrics, PoolObserver};
+use super::timeout::PoolTimeout;
+use super::waiters::{lock, Handoff, Waiter, WaiterQueue};
 use super::Options;
 
-pub(crate) struct SharedPool<C> {
-    url: String,
-    idle_conns: ArrayQueue<Idle<C>>,
-    waiters: SegQueue<Waker>,
+pub(crate) struct SharedPool<C>
+where
+    C: Connect,
+{
+    connect_options: C::Options,
+    idle_conns: SegQueue<Idle<C>>,
+    waiters: WaiterQueue<C>,
     pub(super) size: AtomicU32,
+    max_size: AtomicU32,
+    min_size: AtomicU32,
     is_closed: AtomicBool,
-    options: Options,
+    metrics: Metrics,
+    options: Options<C>,
+
+    // When and with what message opening a connection last failed
+    last_connect_error: Mutex<Option<(Instant, String)>>,
+
+    // Connections created before this are closed instead of being returned to the pool
+    retire_before: Mutex<Option<Instant>>,
+
+    // Tasks in `drain`, woken when the last connection of the closed pool is closed
+    drain_waiters: Mutex<Vec<Arc<Waiter<C>>>>,
 }
 
 impl<C> SharedPool<C>
 where
-    C: Connection,
+    C: Connect,
 {
-    pub fn options(&self) -> &Options {
+    pub fn options(&self) -> &Options<C> {
         &self.options
     }
 
-    pub(super) fn url(&self) -> &str {
-        &self.url
+    pub(super) fn connect_options(&self) -> &C::Options {
+        &self.connect_options
     }
 
     pub(super) fn size(&self) -> u32 {
         self.size.load(Ordering::Acquire)
     }
 
+    pub(super) fn max_size(&self) -> u32 {
+        self.max_size.load(Ordering::Acquire)
+    }
+
+    pub(super) fn min_size(&self) -> u32 {
+        self.min_size.load(Ordering::Acquire)
+    }
+
     pub(super) fn num_idle(&self) -> usize {
         // NOTE: This is very expensive
         self.idle_conns.len()
@@ -53,17 +76,218 @@ where
         self.is_closed.load(Ordering::Acquire)
     }
 
+    pub(super) fn metrics(&self) -> PoolMetrics {
+        self.metrics
+            .snapshot(self.size(), self.num_idle(), self.waiters.len())
+    }
+
+    // Calls the observer of the pool, if there is one
+    fn observe<F>(&self, event: F)
+    where
+        F: FnOnce(&dyn PoolObserver),
+    {
+        if let Some(observer) = &self.options.observer {
+            event(&**observer);
+        }
+    }
+
+    pub(super) fn record_release(&self, checked_out: Duration) {
+        self.metrics.released(checked_out);
+        self.observe(|observer| observer.on_release(checked_out));
+    }
+
+    pub(super) fn record_close(&self, reason: CloseReason) {
+        self.metrics.closed(reason);
+        self.observe(|observer| observer.on_close(reason));
+    }
+
     pub(super) async fn close(&self) {
         self.is_closed.store(true, Ordering::Release);
-        while let Ok(_) = self.idle_conns.pop() {}
-        while let Ok(waker) = self.waiters.pop() {
-            waker.wake();
+        while let Ok(idle) = self.idle_conns.pop() {
+            // the size of the pool is decremented as the connection is dropped
+            drop(Floating::from_idle(idle, self));
+            self.record_close(CloseReason::Closed);
+        }
+
+        self.wake_waiters();
+    }
+
+    // Wakes every waiter, e.g. to let them see that the pool was closed
+    fn wake_waiters(&self) {
+        let waiters: Vec<_> = self.waiters.lock().drain(..).collect();
+
+        for waiter in waiters {
+            waiter.wake();
+        }
+    }
+
+    /// Closes the pool, closing idle connections at once and the connections that are
+    /// checked out as they are returned.
+    ///
+    /// Returns `false` if connections were still checked out at `deadline`.
+    pub(super) async fn drain(&self, deadline: Instant) -> bool {
+        self.is_closed.store(true, Ordering::Release);
+        self.wake_waiters();
+
+        while let Ok(idle) = self.idle_conns.pop() {
+            let _ = Floating::from_idle(idle, self).close().await;
+            self.record_close(CloseReason::Closed);
+        }
+
+        let waiter = Waiter::new();
+        lock(&self.drain_waiters).push(Arc::clone(&waiter));
+
+        let emptied = future::poll_fn(|cx| {
+            // The waker is registered before the size is read, so that the release of the last
+            // connection in between is not missed
+            let _ = waiter.poll_handoff(cx);
+
+            if self.size() == 0 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        });
+
+        let drained = match deadline.checked_duration_since(Instant::now()) {
+            Some(remaining) => timeout(remaining, emptied).await.is_ok(),
+            None => self.size() == 0,
+        };
+
+        lock(&self.drain_waiters).retain(|other| !Arc::ptr_eq(other, &waiter));
+
+        drained
+    }
+
+    pub(super) fn set_max_size(self: &Arc<Self>, max_size: u32) {
+        self.max_size.store(max_size, Ordering::Release);
+
+        // Tasks already waiting may open connections in the room made by a larger maximum
+        {
+            let mut waiters = self.waiters.lock();
+
+            while !waiters.is_empty() && self.try_reserve_size() {
+                if let Some(waiter) = waiters.pop_front() {
+                    waiter.hand(Handoff::Permit);
+                }
+            }
+        }
+
+        // Checked-out connections over the new maximum are closed as they are returned
+        while let Ok(idle) = self.idle_conns.pop() {
+            if !self.give_up_size() {
+                self.push_idle(idle);
+                break;
+            }
+
+            self.retire_excess(idle.into_live());
+        }
+    }
+
+    pub(super) fn set_min_size(&self, min_size: u32) {
+        self.min_size.store(min_size, Ordering::Release);
+    }
+
+    pub(super) fn close_older_than(self: &Arc<Self>, created_before: Instant) {
+        {
+            let mut retire_before = lock(&self.retire_before);
+
+            match *retire_before {
+                Some(before) if before >= created_before => {}
+                _ => *retire_before = Some(created_before),
+            }
+        }
+
+        // Checked-out connections are closed as they are returned
+        self.retire_idle(|idle| idle.created < created_before);
+    }
+
+    /// Whether a connection should be closed instead of being returned to the pool, because
+    /// the pool was closed or the connection is older than [`close_older_than`] allows.
+    ///
+    /// [`close_older_than`]: SharedPool::close_older_than
+    pub(super) fn should_retire(&self, live: &Live<C>) -> bool {
+        if self.is_closed() {
+            return true;
+        }
+
+        match *lock(&self.retire_before) {
+            Some(before) => live.created < before,
+            None => false,
+        }
+    }
+
+    /// Gives up the slot of a connection if the pool is larger than its maximum size, e.g. after
+    /// [`set_max_size`]. The connection must then be closed without releasing its slot again.
+    ///
+    /// The slot is given up right away, so that connections returned at the same time do not
+    /// all see the pool as too large.
+    ///
+    /// [`set_max_size`]: SharedPool::set_max_size
+    pub(super) fn give_up_size(&self) -> bool {
+        let mut size = self.size();
+
+        loop {
+            if size <= self.max_size() {
+                return false;
+            }
+
+            match self
+                .size
+                .compare_exchange(size, size - 1, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => return true,
+                Err(actual) => size = actual,
+            }
+        }
+    }
+
+    /// Closes a connection in the background, giving up its place in the pool.
+    pub(super) fn retire(self: &Arc<Self>, live: Live<C>) {
+        let pool = Arc::clone(self);
+
+        spawn(async move {
+            let _ = live.float(&pool).into_idle().close().await;
+            pool.record_close(CloseReason::Closed);
+        });
+    }
+
+    /// Closes a connection in the background after its slot was [given up](Self::give_up_size).
+    pub(super) fn retire_excess(self: &Arc<Self>, live: Live<C>) {
+        let pool = Arc::clone(self);
+
+        spawn(async move {
+            let _ = live.into_raw().close().await;
+            pool.record_close(CloseReason::Closed);
+        });
+    }
+
+    // Closes the idle connections that match `should_retire`, returning the others
+    fn retire_idle<F>(self: &Arc<Self>, mut should_retire: F)
+    where
+        F: FnMut(&Idle<C>) -> bool,
+    {
+        let (retire, keep) = (0..self.num_idle())
+            .filter_map(|_| self.idle_conns.pop().ok())
+            .partition::<Vec<_>, _>(|idle| should_retire(idle));
+
+        for idle in keep {
+            self.push_idle(idle);
+        }
+
+        for idle in retire {
+            self.retire(idle.into_live());
         }
     }
 
     #[inline]
     pub(super) fn try_acquire(&self) -> Option<Floating<Live<C>>> {
-        Some(self.pop_idle()?.into_live())
+        let conn = self.pop_idle()?.into_live();
+
+        self.metrics.acquired(Duration::from_secs(0));
+        self.observe(|observer| observer.on_acquire(Duration::from_secs(0)));
+
+        Some(conn)
     }
 
     fn pop_idle(&self) -> Option<Floating<Idle<C>>> {
@@ -75,56 +299,177 @@ where
     }
 
     pub(super) fn release(&self, floating: Floating<Live<C>>) {
-        self.idle_conns
-            .push(floating.into_idle().into_leakable())
-            .expect("BUG: connection queue overflow in release()");
-        if let Ok(waker) = self.waiters.pop() {
-            waker.wake();
+        self.push_idle(floating.into_idle().into_leakable());
+    }
+
+    /// Hands an idle connection directly to the oldest waiter, or returns it to the idle
+    /// connections if no task is waiting.
+    fn push_idle(&self, idle: Idle<C>) {
+        let mut waiters = self.waiters.lock();
+
+        match waiters.pop_front() {
+            Some(waiter) => waiter.hand(Handoff::Idle(idle)),
+
+            None => self.idle_conns.push(idle),
+        }
+    }
+
+    /// Hands the slot of a closed connection to the oldest waiter, so that it may open a new
+    /// connection, or decrements the size of the pool if no task is waiting.
+    ///
+    /// If the pool is larger than its maximum size, e.g. after [`set_max_size`], the slot is
+    /// given up in any case.
+    ///
+    /// Once the last connection of a closed pool is closed, the tasks in [`drain`] are woken.
+    ///
+    /// [`set_max_size`]: SharedPool::set_max_size
+    /// [`drain`]: SharedPool::drain
+    fn release_size(&self) {
+        let mut waiters = self.waiters.lock();
+
+        if self.size() <= self.max_size() {
+            if let Some(waiter) = waiters.pop_front() {
+                waiter.hand(Handoff::Permit);
+                return;
+            }
+        }
+
+        let emptied = self.size.fetch_sub(1, Ordering::SeqCst) == 1;
+
+        if emptied && self.is_closed() {
+            for waiter in lock(&self.drain_waiters).iter() {
+                waiter.wake();
+            }
         }
     }
 
     /// Try to atomically increment the pool size for a new connection.
     ///
-    /// Returns `None` if we are at max_size.
-    fn try_increment_size(&self) -> Option<DecrementSizeGuard> {
+    /// Returns `false` if we are at max_size.
+    fn try_reserve_size(&self) -> bool {
         let mut size = self.size();
 
-        while size < self.options.max_size {
+        while size < self.max_size() {
             let new_size = self.size.compare_and_swap(size, size + 1, Ordering::AcqRel);
 
             if new_size == size {
-                return Some(DecrementSizeGuard::new(self));
+                return true;
             }
 
             size = new_size;
         }
 
-        None
+        false
+    }
+
+    fn try_increment_size(&self) -> Option<DecrementSizeGuard> {
+        if self.try_reserve_size() {
+            Some(DecrementSizeGuard::new(self))
+        } else {
+            None
+        }
+    }
+
+    /// Takes an idle connection or a slot to open a new one, or enqueues a waiter if there is
+    /// neither.
+    ///
+    /// Unless `first_in_line` is set, a task that finds other tasks waiting gets in line behind
+    /// them rather than taking a connection they are waiting for.
+    fn take_or_enqueue(&self, first_in_line: bool) -> Take<C> {
+        let mut waiters = self.waiters.lock();
+
+        // Checked while the queue is locked, as `close` wakes the waiters it holds
+        if self.is_closed() {
+            return Take::Closed;
+        }
+
+        if first_in_line || waiters.is_empty() {
+            if let Ok(idle) = self.idle_conns.pop() {
+                return Take::Handoff(Handoff::Idle(idle));
+            }
+
+            if self.try_reserve_size() {
+                return Take::Handoff(Handoff::Permit);
+            }
+        }
+
+        let waiter = Waiter::new();
+
+        if first_in_line {
+            waiters.push_front(Arc::clone(&waiter));
+        } else {
+            waiters.push_back(Arc::clone(&waiter));
+        }
+
+        Take::Wait(waiter)
     }
 
-    /// Wait for a connection, if either `size` drops below `max_size` so we can
-    /// open a new connection, or if an idle connection is returned to the pool.
+    /// Waits until a connection or a slot to open one is handed to `waiter`.
     ///
-    /// Returns an error if `deadline` elapses before we are woken.
-    async fn wait_for_conn(&self, deadline: Instant) -> crate::Result<()> {
-        let mut waker_pushed = false;
+    /// Returns `None` if the pool was closed, or an error if `deadline` elapses first.
+    async fn wait_for_handoff(
+        &self,
+        waiter: Arc<Waiter<C>>,
+        deadline: Instant,
+    ) -> crate::Result<Option<Handoff<C>>> {
+        let mut guard = LeaveQueueGuard {
+            pool: self,
+            waiter: &waiter,
+            done: false,
+        };
 
-        timeout(
+        let handoff = timeout(
             deadline_as_timeout::<C::Database>(deadline)?,
-            // `poll_fn` gets us easy access to a `Waker` that we can push to our queue
-            future::poll_fn(|ctx| -> Poll<()> {
-                if !waker_pushed {
-                    // only push the waker once
-                    self.waiters.push(ctx.waker().to_owned());
-                    waker_pushed = true;
-                    Poll::Pending
-                } else {
-                    Poll::Ready(())
-                }
+            future::poll_fn(|cx| match waiter.poll_handoff(cx) {
+                Poll::Ready(handoff) => Poll::Ready(Some(handoff)),
+                Poll::Pending if self.is_closed() => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
             }),
         )
         .await
-        .map_err(|_| crate::Error::PoolTimedOut(None))
+        .map_err(|_| crate::Error::PoolTimedOut(None))?;
+
+        guard.done = handoff.is_some();
+
+        Ok(handoff)
+    }
+}
+
+// The outcome of `SharedPool::take_or_enqueue`
+enum Take<C> {
+    Handoff(Handoff<C>),
+    Wait(Arc<Waiter<C>>),
+    Closed,
+}
+
+/// Removes a waiter from the queue when it stops waiting without having been handed anything,
+/// e.g. because it timed out or its acquire was cancelled.
+struct LeaveQueueGuard<'a, C>
+where
+    C: Connect,
+{
+    pool: &'a SharedPool<C>,
+    waiter: &'a Arc<Waiter<C>>,
+    done: bool,
+}
+
+impl<C> Drop for LeaveQueueGuard<'_, C>
+where
+    C: Connect,
+{
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+
+        self.pool.waiters.remove(self.waiter);
+
+        // A connection or slot may have been handed over just before the waiter left
+        match self.waiter.take_handoff() {
+            Some(Handoff::Idle(idle)) => self.pool.push_idle(idle),
+            Some(Handoff::Permit) => self.pool.release_size(),
+            None => {}
+        }
     }
 }
 
@@ -132,15 +477,28 @@ impl<C> SharedPool<C>
 where
     C: Connect,
 {
-    pub(super) async fn new_arc(url: &str, options: Options) -> crate::Result<Arc<Self>> {
-        let mut pool = Self {
-            url: url.to_owned(),
-            idle_conns: ArrayQueue::new(options.max_size as usize),
-            waiters: SegQueue::new(),
+    fn new(connect_options: C::Options, options: Options<C>) -> Self {
+        Self {
+            connect_options,
+            idle_conns: SegQueue::new(),
+            waiters: WaiterQueue::new(),
             size: AtomicU32::new(0),
+            max_size: AtomicU32::new(options.max_size),
+            min_size: AtomicU32::new(options.min_size),
             is_closed: AtomicBool::new(false),
+            metrics: Metrics::default(),
             options,
-        };
+            last_connect_error: Mutex::new(None),
+            retire_before: Mutex::new(None),
+            drain_waiters: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(super) async fn new_arc(
+        connect_options: C::Options,
+        options: Options<C>,
+    ) -> crate::Result<Arc<Self>> {
+        let mut pool = Self::new(connect_options, options);
 
         pool.init_min_connections().await?;
 
@@ -151,53 +509,113 @@ where
         Ok(pool)
     }
 
+    // Connections are only opened once they are acquired
+    pub(super) fn new_arc_lazy(connect_options: C::Options, options: Options<C>) -> Arc<Self> {
+        let pool = Arc::new(Self::new(connect_options, options));
+
+        spawn_reaper(&pool);
+
+        pool
+    }
+
     pub(super) async fn acquire<'s>(&'s self) -> crate::Result<Floating<'s, Live<C>>> {
         let start = Instant::now();
-        let deadline = start + self.options.connect_timeout;
-
-        // Unless the pool has been closed ...
-        while !self.is_closed() {
-            // Attempt to immediately acquire a connection. This will return Some
-            // if there is an idle connection in our channel.
-            if let Ok(conn) = self.idle_conns.pop() {
-                let conn = Floating::from_idle(conn, self);
-                if let Some(live) = check_conn(conn, &self.options).await {
-                    return Ok(live);
-                }
+        let result = self
+            .acquire_until(start + self.options.connect_timeout)
+            .await;
+        let wait = start.elapsed();
+
+        match &result {
+            Ok(_) => {
+                self.metrics.acquired(wait);
+                self.observe(|observer| observer.on_acquire(wait));
             }
 
-            if let Some(guard) = self.try_increment_size() {
-                // pool has slots available; open a new connection
-                match self.connect(deadline, guard).await {
-                    Ok(Some(conn)) => return Ok(conn),
-                    // [size] is internally decremented on _retry_ and _error_
-                    Ok(None) => continue,
-                    Err(e) => return Err(e),
-                }
+            Err(Error::PoolTimedOut(_)) => {
+                self.metrics.acquire_timed_out(wait);
+                self.observe(|observer| observer.on_acquire_timeout(wait));
+
+                return Err(self.timed_out(start));
             }
 
-            // Wait for a connection to become available (or we are allowed to open a new one)
-            // Returns an error if `deadline` passes
-            self.wait_for_conn(deadline).await?;
+            Err(_) => {}
         }
 
-        Err(Error::PoolClosed)
+        result
+    }
+
+    // The error of an acquire that started at `start` and timed out, with the state of the pool
+    fn timed_out(&self, start: Instant) -> Error {
+        let last_connect_error = lock(&self.last_connect_error)
+            .as_ref()
+            .filter(|(at, _)| *at >= start)
+            .map(|(_, message)| message.clone());
+
+        Error::PoolTimedOut(Some(Box::new(PoolTimeout {
+            size: self.size(),
+            max_size: self.max_size(),
+            idle: self.num_idle(),
+            // the task that timed out has already left the queue
+            waiters: self.waiters.len() + 1,
+            elapsed: start.elapsed(),
+            last_connect_error,
+        })))
+    }
+
+    async fn acquire_until<'s>(
+        &'s self,
+        deadline: Instant,
+    ) -> crate::Result<Floating<'s, Live<C>>> {
+        // Set once this task has been handed a connection or slot that turned out to be
+        // unusable, so that it keeps its place at the front of the line
+        let mut first_in_line = false;
+
+        loop {
+            let handoff = match self.take_or_enqueue(first_in_line) {
+                Take::Handoff(handoff) => handoff,
+
+                // Wait for a connection to be released (or for a slot to open a new one)
+                // Returns an error if `deadline` passes
+                Take::Wait(waiter) => match self.wait_for_handoff(waiter, deadline).await? {
+                    Some(handoff) => handoff,
+                    None => return Err(Error::PoolClosed),
+                },
+
+                Take::Closed => return Err(Error::PoolClosed),
+            };
+
+            first_in_line = true;
+
+            match handoff {
+                Handoff::Idle(idle) => {
+                    let conn = Floating::from_idle(idle, self);
+                    if let Some(live) = check_conn(self, conn).await {
+                        return Ok(live);
+                    }
+                }
+
+                Handoff::Permit => {
+                    // the size of the pool already counts the new connection
+                    let guard = DecrementSizeGuard::new(self);
+
+                    // [size] is internally decremented on _error_
+                    return self.connect(deadline, guard).await;
+                }
+            }
+        }
     }
 
     // takes `&mut self` so this can only be called during init
     async fn init_min_connections(&mut self) -> crate::Result<()> {
-        for _ in 0..self.options.min_size {
+        for _ in 0..self.min_size() {
             let deadline = Instant::now() + self.options.connect_timeout;
 
             // this guard will prevent us from exceeding `max_size`
             while let Some(guard) = self.try_increment_size() {
                 // [connect] will raise an error when past deadline
-                // [connect] returns None if its okay to retry
-                if let Some(conn) = self.connect(deadline, guard).await? {
-                    self.idle_conns
-                        .push(conn.into_idle().into_leakable())
-                        .expect("BUG: connection queue overflow in init_min_connections");
-                }
+                let conn = self.connect(deadline, guard).await?;
+
+                self.idle_conns.push(conn.into_idle().into_leakable());
             }
         }
 
@@ -208,66 +626,178 @@ where
         &'s self,
         deadline: Instant,
         guard: DecrementSizeGuard<'s>,
-    ) -> crate::Result<Option<Floating<'s, Live<C>>>> {
-        if self.is_closed() {
-            return Err(Error::PoolClosed);
+    ) -> crate::Result<Floating<'s, Live<C>>> {
+        let mut attempt = 1;
+
+        // The last error, and whether every attempt failed with the same error as it
+        let mut last_error: Option<Error> = None;
+        let mut same_errors = true;
+
+        loop {
+            if self.is_closed() {
+                return Err(Error::PoolClosed);
+            }
+
+            let timeout = match super::deadline_as_timeout::<C::Database>(deadline) {
+                Ok(timeout) => timeout,
+                Err(error) => return Err(timed_out(last_error, same_errors, error)),
+            };
+
+            // result here is `Result<Result<C, Error>, TimeoutError>`
+            let result = crate::runtime::timeout(timeout, self.connect_raw()).await;
+
+            let error = match result {
+                // successfully established connection
+                Ok(Ok(raw)) => return self.set_up(raw, guard).await,
+
+                Ok(Err(error)) => error,
+
+                // timed out
+                Err(e) => {
+                    let error = crate::Error::PoolTimedOut(Some(Box::new(e)));
+
+                    return Err(timed_out(last_error, same_errors, error));
+                }
+            };
+
+            self.metrics.connect_failed();
+            self.observe(|observer| observer.on_connect_error(&error));
+
+            *lock(&self.last_connect_error) = Some((Instant::now(), error.to_string()));
+
+            // Errors that are not transient, e.g. failed authentication, are returned at once
+            if !self
+                .options
+                .connect_retry
+                .should_retry_connect(attempt, &error)
+            {
+                return Err(error);
+            }
+
+            if let Some(last_error) = &last_error {
+                same_errors &= is_same_error(last_error, &error);
+            }
+
+            let backoff = self.options.connect_retry.backoff_after(attempt);
+
+            if Instant::now() + backoff >= deadline {
+                return Err(timed_out(
+                    Some(error),
+                    same_errors,
+                    crate::Error::PoolTimedOut(None),
+                ));
+            }
+
+            log::debug!(
+                "retrying to connect in {:?} after error: {}",
+                backoff,
+                error
+            );
+
+            last_error = Some(error);
+
+            sleep(backoff).await;
+            attempt += 1;
         }
+    }
 
-        let timeout = super::deadline_as_timeout::<C::Database>(deadline)?;
+    // Prepares a new connection for the pool
+    async fn set_up<'s>(
+        &'s self,
+        mut raw: C,
+        guard: DecrementSizeGuard<'s>,
+    ) -> crate::Result<Floating<'s, Live<C>>> {
+        self.metrics.connected();
+        self.observe(|observer| observer.on_connect());
 
-        // result here is `Result<Result<C, Error>, TimeoutError>`
-        match crate::runtime::timeout(timeout, C::connect(&self.url)).await {
-            // successfully established connection
-            Ok(Ok(raw)) => Ok(Some(Floating::new_live(raw, guard))),
+        if let Some(capacity) = self.options.statement_cache_capacity {
+            raw.set_statement_cache_capacity(capacity);
+        }
 
-            // an IO error while connecting is assumed to be the system starting up
-            Ok(Err(crate::Error::Io(_))) => Ok(None),
+        if let Some(settings) = &self.options.log_settings {
+            raw.set_log_settings(settings.clone());
+        }
 
-            // TODO: Handle other database "boot period"s
+        if let Some(after_connect) = &self.options.after_connect {
+            if let Err(error) = after_connect(&mut raw).await {
+                let _ = raw.close().await;
+                self.record_close(CloseReason::Error);
 
-            // [postgres] the database system is starting up
-            // TODO: Make this check actually check if this is postgres
-            Ok(Err(crate::Error::Database(error))) if error.code() == Some("57P03") => Ok(None),
+                return Err(error);
+            }
+        }
+
+        Ok(Floating::new_live(raw, guard))
+    }
 
-            // Any other error while connection should immediately
-            // terminate and bubble the error up
-            Ok(Err(e)) => Err(e),
+    // Opens a connection with the credentials of the provider, if there is one
+    async fn connect_raw(&self) -> crate::Result<C> {
+        match &self.options.credentials {
+            Some(provider) => {
+                let credentials = provider().await?;
+                let options = C::with_credentials(&self.connect_options, &credentials)?;
 
-            // timed out
-            Err(e) => Err(crate::Error::PoolTimedOut(Some(Box::new(e)))),
+                C::connect_with(&options).await
+            }
+
+            None => C::connect_with(&self.connect_options).await,
         }
     }
 }
 
+// The error of connect attempts that ran out of time: the error of the attempts if they all
+// failed the same way, or `timeout` otherwise
+fn timed_out(last_error: Option<Error>, same_errors: bool, timeout: Error) -> Error {
+    match last_error {
+        Some(error) if same_errors => error,
+        _ => timeout,
+    }
+}
+
+fn is_same_error(a: &Error, b: &Error) -> bool {
+    match (a, b) {
+        (Error::Io(a), Error::Io(b)) => a.kind() == b.kind(),
+        (Error::Database(a), Error::Database(b)) => a.code() == b.code(),
+        _ => a.to_string() == b.to_string(),
+    }
+}
+
 // NOTE: Function names here are bizzare. Helpful help would be appreciated.
 
-fn is_beyond_lifetime<C>(live: &Live<C>, options: &Options) -> bool {
+fn is_beyond_lifetime<C>(live: &Live<C>, options: &Options<C>) -> bool {
     // check if connection was within max lifetime (or not set)
     options
         .max_lifetime
         .map_or(false, |max| live.created.elapsed() > max)
 }
 
-fn is_beyond_idle<C>(idle: &Idle<C>, options: &Options) -> bool {
+fn is_beyond_idle<C>(idle: &Idle<C>, options: &Options<C>) -> bool {
     // if connection wasn't idle too long (or not set)
     options
         .idle_timeout
         .map_or(false, |timeout| idle.since.elapsed() > timeout)
 }
 
-async fn check_conn<'s: 'p, 'p, C>(
+async fn check_conn<'s, C>(
+    pool: &'s SharedPool<C>,
     mut conn: Floating<'s, Idle<C>>,
-    options: &'p Options,
 ) -> Option<Floating<'s, Live<C>>>
 where
-    C: Connection,
+    C: Connect,
 {
+    let options = &pool.options;
+
     // If the connection we pulled has expired, close the connection and
     // immediately create a new connection
     if is_beyond_lifetime(&conn, options) {
         // we're closing the connection either way
         // close the connection but don't really care about the result
         let _ = conn.close().await;
+        pool.record_close(CloseReason::MaxLifetime);
+        return None;
+    } else if pool.should_retire(&conn) {
+        let _ = conn.close().await;
+        pool.record_close(CloseReason::Closed);
         return None;
     } else if options.test_on_acquire {
         // TODO: Check on acquire should be a configuration setting
@@ -278,10 +808,30 @@ where
             // the error itself here isn't necessarily unexpected so WARN is too strong
             log::info!("ping on idle connection returned error: {}", e);
             // connection is broken so don't try to close nicely
+            pool.record_close(CloseReason::PingFailed);
             return None;
         }
     }
 
+    if let Some(before_acquire) = &options.before_acquire {
+        match before_acquire(conn.raw_mut()).await {
+            Ok(true) => {}
+
+            Ok(false) => {
+                let _ = conn.close().await;
+                pool.record_close(CloseReason::Rejected);
+                return None;
+            }
+
+            Err(e) => {
+                log::info!("before_acquire hook returned error: {}", e);
+                let _ = conn.close().await;
+                pool.record_close(CloseReason::Error);
+                return None;
+            }
+        }
+    }
+
     // No need to re-connect; connection is alive or we don't care
     Some(conn.into_live())
 }
@@ -289,7 +839,7 @@ where
 /// if `max_lifetime` or `idle_timeout` is set, spawn a task that reaps senescent connections
 fn spawn_reaper<C>(pool: &Arc<SharedPool<C>>)
 where
-    C: Connection,
+    C: Connect,
 {
     let period = match (pool.options.max_lifetime, pool.options.idle_timeout) {
         (Some(it), None) | (None, Some(it)) => it,
@@ -304,7 +854,7 @@ where
     spawn(async move {
         while !pool.is_closed.load(Ordering::Acquire) {
             // reap at most the current size minus the minimum idle
-            let max_reaped = pool.size().saturating_sub(pool.options.min_size);
+            let max_reaped = pool.size().saturating_sub(pool.min_size());
 
             // collect connections to reap
             let (reap, keep) = (0..max_reaped)
@@ -315,14 +865,19 @@ where
                 });
 
             for conn in keep {
-                // return these connections to the pool first
-                pool.idle_conns
-                    .push(conn.into_leakable())
-                    .expect("BUG: connection queue overflow in spawn_reaper");
+                // return these connections to the pool (or a waiting task) first
+                pool.push_idle(conn.into_leakable());
             }
 
             for conn in reap {
+                let reason = if is_beyond_lifetime(&conn, &pool.options) {
+                    CloseReason::MaxLifetime
+                } else {
+                    CloseReason::IdleTimeout
+                };
+
                 let _ = conn.close().await;
+                pool.record_close(reason);
             }
 
             sleep(period).await;
@@ -333,25 +888,48 @@ where
 /// RAII guard returned by `Pool::try_increment_size()` and others.
 ///
 /// Will decrement the pool size if dropped, to avoid semantically "leaking" connections
-/// (where the pool thinks it has more connections than it does).
+/// (where the pool thinks it has more connections than it does). If a task is waiting for a
+/// connection, the slot is handed to it instead.
 pub(in crate::pool) struct DecrementSizeGuard<'a> {
-    size: &'a AtomicU32,
-    waiters: &'a SegQueue<Waker>,
+    pool: &'a dyn ReleaseSize,
     dropped: bool,
 }
 
+/// Lets a [`DecrementSizeGuard`] give back its slot without knowing the connection type of
+/// the pool.
+pub(in crate::pool) trait ReleaseSize: Send + Sync {
+    fn release_size(&self);
+}
+
+impl<C> ReleaseSize for SharedPool<C>
+where
+    C: Connect,
+{
+    fn release_size(&self) {
+        SharedPool::release_size(self)
+    }
+}
+
 impl<'a> DecrementSizeGuard<'a> {
-    pub fn new<C>(pool: &'a SharedPool<C>) -> Self {
+    pub fn new<C>(pool: &'a SharedPool<C>) -> Self
+    where
+        C: Connect,
+    {
         Self {
-            size: &pool.size,
-            waiters: &pool.waiters,
+            pool,
             dropped: false,
         }
     }
 
-    /// Return `true` if the internal references point to the same fields in `SharedPool`.
-    pub fn same_pool<C>(&self, pool: &'a SharedPool<C>) -> bool {
-        ptr::eq(self.size, &pool.size) && ptr::eq(self.waiters, &pool.waiters)
+    /// Return `true` if the guard belongs to `pool`.
+    pub fn same_pool<C>(&self, pool: &'a SharedPool<C>) -> bool
+    where
+        C: Connect,
+    {
+        ptr::eq(
+            self.pool as *const dyn ReleaseSize as *const u8,
+            pool as *const SharedPool<C> as *const u8,
+        )
     }
 
     pub fn cancel(self) {
@@ -363,9 +941,6 @@ impl Drop for DecrementSizeGuard<'_> {
     fn drop(&mut self) {
         assert!(!self.dropped, "double-dropped!");
         self.dropped = true;
-        self.size.fetch_sub(1, Ordering::SeqCst);
-        if let Ok(waker) = self.waiters.pop() {
-            waker.wake();
-        }
+        self.pool.release_size();
     }
 }
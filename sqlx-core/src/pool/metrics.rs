@@ -0,0 +1,331 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::error::Error;
+
+// Upper bounds of the buckets of a histogram; longer durations fall into a final bucket
+const BUCKETS: [Duration; 8] = [
+    Duration::from_millis(1),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+];
+
+/// Why the pool closed a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The connection was older than the [`max_lifetime`](crate::pool::Builder::max_lifetime)
+    /// of the pool.
+    MaxLifetime,
+
+    /// The connection was idle for longer than the
+    /// [`idle_timeout`](crate::pool::Builder::idle_timeout) of the pool.
+    IdleTimeout,
+
+    /// The connection did not respond to the ping of
+    /// [`test_on_acquire`](crate::pool::Builder::test_on_acquire).
+    PingFailed,
+
+    /// A hook of the pool rejected the connection.
+    Rejected,
+
+    /// An error occurred while resetting the connection, e.g. in a hook of the pool or when
+    /// rolling back its open transaction.
+    Error,
+
+    /// The connection was released after an error that left it unusable, e.g. because its
+    /// server shut down or stopped being the primary.
+    Broken,
+
+    /// The connection or the pool was closed explicitly.
+    Closed,
+}
+
+impl CloseReason {
+    const ALL: [CloseReason; 7] = [
+        CloseReason::MaxLifetime,
+        CloseReason::IdleTimeout,
+        CloseReason::PingFailed,
+        CloseReason::Rejected,
+        CloseReason::Error,
+        CloseReason::Broken,
+        CloseReason::Closed,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Observes the events of a pool as they happen, e.g. to forward them to a metrics library.
+///
+/// Every method does nothing by default. Methods are called from the task that caused the
+/// event and should return quickly.
+///
+/// Set with [`Builder::observer`](crate::pool::Builder::observer).
+#[allow(unused_variables)]
+pub trait PoolObserver: Send + Sync + 'static {
+    /// Called when a connection is acquired, with the time spent waiting for it.
+    fn on_acquire(&self, wait: Duration) {}
+
+    /// Called when acquiring a connection timed out, with the time spent waiting.
+    fn on_acquire_timeout(&self, wait: Duration) {}
+
+    /// Called when a new connection is opened.
+    fn on_connect(&self) {}
+
+    /// Called when opening a new connection failed.
+    fn on_connect_error(&self, error: &Error) {}
+
+    /// Called when a connection is returned to the pool, with the time it was checked out.
+    fn on_release(&self, checked_out: Duration) {}
+
+    /// Called when a connection is closed by the pool.
+    fn on_close(&self, reason: CloseReason) {}
+}
+
+/// A snapshot of the metrics of a pool, returned by [`Pool::metrics`].
+///
+/// Counters start at zero when the pool is built and never decrease.
+///
+/// [`Pool::metrics`]: crate::pool::Pool::metrics
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PoolMetrics {
+    /// The number of connections managed by the pool, idle or checked out.
+    pub size: u32,
+
+    /// The number of idle connections.
+    pub idle: usize,
+
+    /// The number of tasks waiting for a connection.
+    pub waiters: usize,
+
+    /// The number of connections acquired.
+    pub acquired: u64,
+
+    /// The number of times acquiring a connection timed out.
+    pub acquire_timeouts: u64,
+
+    /// The time spent waiting to acquire a connection.
+    pub acquire_wait: Histogram,
+
+    /// The number of connections opened.
+    pub connections_opened: u64,
+
+    /// The number of times opening a connection failed.
+    pub connect_errors: u64,
+
+    /// The time connections were checked out of the pool.
+    pub checkout_time: Histogram,
+
+    closed: [u64; 7],
+}
+
+impl PoolMetrics {
+    /// The number of connections closed for the given reason.
+    pub fn connections_closed(&self, reason: CloseReason) -> u64 {
+        self.closed[reason.index()]
+    }
+
+    /// The number of connections closed for any reason.
+    pub fn total_connections_closed(&self) -> u64 {
+        self.closed.iter().sum()
+    }
+}
+
+/// A histogram of durations, with buckets from 1 millisecond to 5 seconds.
+#[derive(Debug, Clone, Default)]
+pub struct Histogram {
+    count: u64,
+    sum: Duration,
+    max: Duration,
+    buckets: [u64; BUCKETS.len() + 1],
+}
+
+impl Histogram {
+    /// The number of recorded durations.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The sum of the recorded durations.
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+
+    /// The longest recorded duration.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// The mean of the recorded durations, or `None` if none were recorded.
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(Duration::from_micros(
+            (self.sum.as_micros() / u128::from(self.count)) as u64,
+        ))
+    }
+
+    /// The number of recorded durations in each bucket, with the (inclusive) upper bound of the
+    /// bucket. The upper bound of the last bucket is `None`.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        BUCKETS
+            .iter()
+            .copied()
+            .map(Some)
+            .chain(Some(None))
+            .zip(self.buckets.iter().copied())
+    }
+}
+
+// Records a histogram with atomics so that it can be updated without a lock
+#[derive(Default)]
+struct AtomicHistogram {
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+    max_micros: AtomicU64,
+    buckets: [AtomicU64; BUCKETS.len() + 1],
+}
+
+impl AtomicHistogram {
+    fn record(&self, duration: Duration) {
+        let micros = duration.as_micros() as u64;
+        let bucket = BUCKETS
+            .iter()
+            .position(|bound| duration <= *bound)
+            .unwrap_or(BUCKETS.len());
+
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+        self.max_micros.fetch_max(micros, Ordering::Relaxed);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> Histogram {
+        let mut buckets = [0; BUCKETS.len() + 1];
+
+        for (bucket, count) in buckets.iter_mut().zip(&self.buckets) {
+            *bucket = count.load(Ordering::Relaxed);
+        }
+
+        Histogram {
+            count: self.count.load(Ordering::Relaxed),
+            sum: Duration::from_micros(self.sum_micros.load(Ordering::Relaxed)),
+            max: Duration::from_micros(self.max_micros.load(Ordering::Relaxed)),
+            buckets,
+        }
+    }
+}
+
+/// The counters of a pool, updated as its events happen.
+#[derive(Default)]
+pub(super) struct Metrics {
+    acquired: AtomicU64,
+    acquire_timeouts: AtomicU64,
+    acquire_wait: AtomicHistogram,
+    connections_opened: AtomicU64,
+    connect_errors: AtomicU64,
+    checkout_time: AtomicHistogram,
+    closed: [AtomicU64; 7],
+}
+
+impl Metrics {
+    pub(super) fn acquired(&self, wait: Duration) {
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        self.acquire_wait.record(wait);
+    }
+
+    pub(super) fn acquire_timed_out(&self, wait: Duration) {
+        self.acquire_timeouts.fetch_add(1, Ordering::Relaxed);
+        self.acquire_wait.record(wait);
+    }
+
+    pub(super) fn connected(&self) {
+        self.connections_opened.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn connect_failed(&self) {
+        self.connect_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn released(&self, checked_out: Duration) {
+        self.checkout_time.record(checked_out);
+    }
+
+    pub(super) fn closed(&self, reason: CloseReason) {
+        self.closed[reason.index()].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn snapshot(&self, size: u32, idle: usize, waiters: usize) -> PoolMetrics {
+        let mut closed = [0; 7];
+
+        for reason in CloseReason::ALL.iter() {
+            closed[reason.index()] = self.closed[reason.index()].load(Ordering::Relaxed);
+        }
+
+        PoolMetrics {
+            size,
+            idle,
+            waiters,
+            acquired: self.acquired.load(Ordering::Relaxed),
+            acquire_timeouts: self.acquire_timeouts.load(Ordering::Relaxed),
+            acquire_wait: self.acquire_wait.snapshot(),
+            connections_opened: self.connections_opened.load(Ordering::Relaxed),
+            connect_errors: self.connect_errors.load(Ordering::Relaxed),
+            checkout_time: self.checkout_time.snapshot(),
+            closed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{CloseReason, Metrics};
+
+    #[test]
+    fn it_records_durations_into_buckets() {
+        let metrics = Metrics::default();
+
+        metrics.acquired(Duration::from_micros(500));
+        metrics.acquired(Duration::from_millis(5));
+        metrics.acquired(Duration::from_millis(7));
+        metrics.acquired(Duration::from_secs(10));
+
+        let histogram = metrics.snapshot(0, 0, 0).acquire_wait;
+        let buckets: Vec<_> = histogram.buckets().collect();
+
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(histogram.max(), Duration::from_secs(10));
+        assert_eq!(histogram.sum(), Duration::from_micros(10_012_500));
+        assert_eq!(buckets[0], (Some(Duration::from_millis(1)), 1));
+        assert_eq!(buckets[1], (Some(Duration::from_millis(5)), 1));
+        assert_eq!(buckets[2], (Some(Duration::from_millis(10)), 1));
+        assert_eq!(buckets[8], (None, 1));
+    }
+
+    #[test]
+    fn it_counts_closed_connections_by_reason() {
+        let metrics = Metrics::default();
+
+        metrics.closed(CloseReason::IdleTimeout);
+        metrics.closed(CloseReason::IdleTimeout);
+        metrics.closed(CloseReason::PingFailed);
+
+        let snapshot = metrics.snapshot(0, 0, 0);
+
+        assert_eq!(snapshot.connections_closed(CloseReason::IdleTimeout), 2);
+        assert_eq!(snapshot.connections_closed(CloseReason::PingFailed), 1);
+        assert_eq!(snapshot.connections_closed(CloseReason::MaxLifetime), 0);
+        assert_eq!(snapshot.total_connections_closed(), 3);
+    }
+}
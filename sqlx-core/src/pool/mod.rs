@@ -6,23 +6,38 @@ use std::{
     time::{Duration, Instant},
 };
 
+use futures_core::future::BoxFuture;
+
 use crate::connection::Connect;
 use crate::database::Database;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 
 use self::inner::SharedPool;
 use self::options::Options;
 
 mod connection;
+mod credentials;
 mod executor;
 mod inner;
+mod metrics;
 mod options;
+mod retry;
+mod routed;
+mod timeout;
+mod waiters;
 
 pub use self::connection::PoolConnection;
+pub use self::credentials::Credentials;
+pub use self::metrics::{CloseReason, Histogram, PoolMetrics, PoolObserver};
 pub use self::options::Builder;
+pub use self::retry::RetryPolicy;
+pub use self::routed::{ReplicaSelection, RoutedPool, RoutedPoolBuilder};
+pub use self::timeout::PoolTimeout;
 
 /// A pool of database connections.
-pub struct Pool<C>(pub(crate) Arc<SharedPool<C>>);
+pub struct Pool<C>(pub(crate) Arc<SharedPool<C>>)
+where
+    C: Connect;
 
 impl<C> Pool<C>
 where
@@ -39,12 +54,22 @@ where
         Self::builder().build(url).await
     }
 
-    async fn with_options(url: &str, options: Options) -> crate::Result<Self> {
-        let inner = SharedPool::<C>::new_arc(url, options).await?;
+    /// Creates a connection pool with the default configuration, establishing connections
+    /// with the given options.
+    pub async fn new_with(connect_options: C::Options) -> crate::Result<Self> {
+        Self::builder().build_with(connect_options).await
+    }
+
+    async fn with_options(connect_options: C::Options, options: Options<C>) -> crate::Result<Self> {
+        let inner = SharedPool::<C>::new_arc(connect_options, options).await?;
 
         Ok(Pool(inner))
     }
 
+    fn with_options_lazy(connect_options: C::Options, options: Options<C>) -> Self {
+        Pool(SharedPool::<C>::new_arc_lazy(connect_options, options))
+    }
+
     /// Returns a [Builder] to configure a new connection pool.
     pub fn builder() -> Builder<C> {
         Builder::new()
@@ -52,7 +77,12 @@ where
 
     /// Retrieves a connection from the pool.
     ///
-    /// Waits for at most the configured connection timeout before returning an error.
+    /// Waits for at most the configured connection timeout before returning an error. If
+    /// every attempt to open a connection in that time failed with the same error, that error
+    /// is returned; otherwise, the error is [`PoolTimedOut`] with a [`PoolTimeout`] describing
+    /// the state of the pool.
+    ///
+    /// [`PoolTimedOut`]: crate::error::Error::PoolTimedOut
     pub async fn acquire(&self) -> crate::Result<PoolConnection<C>> {
         self.0.acquire().await.map(|conn| conn.attach(&self.0))
     }
@@ -69,6 +99,101 @@ where
         Ok(Transaction::new(0, self.acquire().await?).await?)
     }
 
+    /// Retrieves a new connection and immediately begins a new transaction with the given
+    /// options.
+    pub async fn begin_with(
+        &self,
+        options: TransactionOptions,
+    ) -> crate::Result<Transaction<PoolConnection<C>>> {
+        Transaction::with_options(self.acquire().await?, options).await
+    }
+
+    /// Runs `f` in a new transaction, retrying the transaction if it fails with an error that
+    /// `policy` considers retryable (by default, a serialization failure or deadlock).
+    ///
+    /// The transaction is committed if `f` returns `Ok` and rolled back if it returns `Err`.
+    /// Each retry runs `f` again in a new transaction after waiting for the backoff of the
+    /// policy. The error of the last attempt is returned unchanged.
+    ///
+    /// Use [`transaction_with_retry_with`](Pool::transaction_with_retry_with) to begin the
+    /// transactions with options, e.g. a serializable isolation level.
+    pub async fn transaction_with_retry<F, T>(&self, policy: &RetryPolicy, f: F) -> crate::Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Transaction<PoolConnection<C>>) -> BoxFuture<'t, crate::Result<T>>,
+    {
+        self.transaction_with_retry_with(policy, TransactionOptions::default(), f)
+            .await
+    }
+
+    /// Runs `f` like [`transaction_with_retry`](Pool::transaction_with_retry), beginning every
+    /// attempt with the given options.
+    ///
+    /// ```rust,ignore
+    /// let options = TransactionOptions {
+    ///     isolation: Some(IsolationLevel::Serializable),
+    ///     ..TransactionOptions::default()
+    /// };
+    ///
+    /// let balance = pool
+    ///     .transaction_with_retry_with(&RetryPolicy::new(), options, |tx| {
+    ///         Box::pin(async move {
+    ///             sqlx::query_as::<_, (i64,)>("UPDATE accounts SET balance = balance - 10 RETURNING balance")
+    ///                 .fetch_one(tx)
+    ///                 .await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn transaction_with_retry_with<F, T>(
+        &self,
+        policy: &RetryPolicy,
+        options: TransactionOptions,
+        mut f: F,
+    ) -> crate::Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Transaction<PoolConnection<C>>) -> BoxFuture<'t, crate::Result<T>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            match self.run_transaction(options, &mut f).await {
+                Err(error) if policy.should_retry(attempt, &error) => {
+                    crate::runtime::sleep(policy.backoff_after(attempt)).await;
+
+                    attempt += 1;
+                }
+
+                result => return result,
+            }
+        }
+    }
+
+    async fn run_transaction<F, T>(
+        &self,
+        options: TransactionOptions,
+        f: &mut F,
+    ) -> crate::Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Transaction<PoolConnection<C>>) -> BoxFuture<'t, crate::Result<T>>,
+    {
+        let mut tx = self.begin_with(options).await?;
+
+        match f(&mut tx).await {
+            Ok(value) => {
+                tx.commit().await?;
+
+                Ok(value)
+            }
+
+            Err(error) => {
+                // The error of the transaction is more useful than a failure to roll it back
+                let _ = tx.rollback().await;
+
+                Err(error)
+            }
+        }
+    }
+
     /// Ends the use of a connection pool. Prevents any new connections
     /// and will close all active connections when they are returned to the pool.
     ///
@@ -77,6 +202,39 @@ where
         self.0.close().await;
     }
 
+    /// Closes the pool like [`close`](Pool::close), then waits until every checked-out
+    /// connection has been returned and closed, or until `deadline`.
+    ///
+    /// No connections are handed out once this is called. Returns `false` if connections were
+    /// still checked out at the deadline; they are closed as they are returned in any case.
+    pub async fn drain(&self, deadline: Instant) -> bool {
+        self.0.drain(deadline).await
+    }
+
+    /// Changes the maximum size of the pool while it is running.
+    ///
+    /// When shrinking, idle connections over the new maximum are closed right away and
+    /// checked-out connections over it are closed as they are returned.
+    pub fn set_max_size(&self, max_size: u32) {
+        self.0.set_max_size(max_size);
+    }
+
+    /// Changes the minimum size of the pool while it is running.
+    ///
+    /// No connections are opened to reach it; it only keeps idle connections from being reaped.
+    pub fn set_min_size(&self, min_size: u32) {
+        self.0.set_min_size(min_size);
+    }
+
+    /// Closes every connection that was opened before `created_before`, e.g. to pick up new
+    /// credentials or to move off a database that failed over.
+    ///
+    /// Idle connections are closed right away and checked-out connections are closed as they
+    /// are returned. New connections are opened on demand.
+    pub fn close_older_than(&self, created_before: Instant) {
+        self.0.close_older_than(created_before);
+    }
+
     /// Returns `true` if [`.close()`][Pool::close] has been called on the pool, `false` otherwise.
     pub fn is_closed(&self) -> bool {
         self.0.is_closed()
@@ -92,9 +250,15 @@ where
         self.0.num_idle()
     }
 
-    /// Returns the configured maximum pool size.
+    /// Returns a snapshot of the metrics of the pool: its current size and waiters, and counters
+    /// of acquired, opened and closed connections since it was built.
+    pub fn metrics(&self) -> PoolMetrics {
+        self.0.metrics()
+    }
+
+    /// Returns the maximum pool size, as configured or last [set](Pool::set_max_size).
     pub fn max_size(&self) -> u32 {
-        self.0.options().max_size
+        self.0.max_size()
     }
 
     /// Returns the maximum time spent acquiring a new connection before an error is returned.
@@ -102,9 +266,10 @@ where
         self.0.options().connect_timeout
     }
 
-    /// Returns the configured minimum idle connection count.
+    /// Returns the minimum idle connection count, as configured or last
+    /// [set](Pool::set_min_size).
     pub fn min_size(&self) -> u32 {
-        self.0.options().min_size
+        self.0.min_size()
     }
 
     /// Returns the configured maximum connection lifetime.
@@ -119,7 +284,10 @@ where
 }
 
 /// Returns a new [Pool] tied to the same shared connection pool.
-impl<C> Clone for Pool<C> {
+impl<C> Clone for Pool<C>
+where
+    C: Connect,
+{
     fn clone(&self) -> Self {
         Self(Arc::clone(&self.0))
     }
@@ -131,7 +299,7 @@ where
 {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("Pool")
-            .field("url", &self.0.url())
+            .field("connect_options", self.0.connect_options())
             .field("size", &self.0.size())
             .field("num_idle", &self.0.num_idle())
             .field("is_closed", &self.0.is_closed())
@@ -158,5 +326,7 @@ fn assert_pool_traits() {
     fn assert_pool<C: Connect>() {
         assert_send_sync::<Pool<C>>();
         assert_clone::<Pool<C>>();
+        assert_send_sync::<RoutedPool<C>>();
+        assert_clone::<RoutedPool<C>>();
     }
 }
@@ -1,13 +1,21 @@
-use std::{marker::PhantomData, time::Duration};
+use std::{fmt, sync::Arc, time::Duration};
 
-use super::Pool;
+use futures_core::future::BoxFuture;
+
+use super::{Credentials, Pool, PoolObserver, RetryPolicy};
 use crate::connection::Connect;
 use crate::database::Database;
+use crate::logging::LogSettings;
+
+// Runs on a connection of the pool at a point in its lifecycle
+type Hook<C, T> = dyn for<'c> Fn(&'c mut C) -> BoxFuture<'c, crate::Result<T>> + Send + Sync;
+
+// Supplies the credentials of each new connection
+type CredentialProvider = dyn Fn() -> BoxFuture<'static, crate::Result<Credentials>> + Send + Sync;
 
 /// Builder for [Pool].
 pub struct Builder<C> {
-    phantom: PhantomData<C>,
-    options: Options,
+    options: Options<C>,
 }
 
 impl<C> Builder<C>
@@ -19,7 +27,6 @@ where
     /// See the source of this method for current defaults.
     pub(crate) fn new() -> Self {
         Self {
-            phantom: PhantomData,
             options: Options {
                 // pool a maximum of 10 connections to the same database
                 max_size: 10,
@@ -27,6 +34,8 @@ where
                 min_size: 0,
                 // try to connect for 10 seconds before erroring
                 connect_timeout: Duration::from_secs(60),
+                // retry transient errors with a backoff of up to 5 seconds, until the timeout
+                connect_retry: RetryPolicy::connect_default(),
                 // reap connections that have been alive > 30 minutes
                 // prevents unbounded live-leaking of memory due to naive prepared statement caching
                 // see src/cache.rs for context
@@ -35,6 +44,15 @@ where
                 idle_timeout: None,
                 // If true, test the health of a connection on acquire
                 test_on_acquire: true,
+                // use the capacity from the connection URL (or the driver default)
+                statement_cache_capacity: None,
+                // use each connection's default log settings
+                log_settings: None,
+                after_connect: None,
+                before_acquire: None,
+                after_release: None,
+                credentials: None,
+                observer: None,
             },
         }
     }
@@ -53,6 +71,32 @@ where
         self
     }
 
+    /// Set when and how often opening a connection is retried after an error.
+    ///
+    /// By default, transient errors such as a refused connection or a database that is still
+    /// starting up are retried until the [`connect_timeout`] elapses, waiting 50 milliseconds
+    /// before the first retry and doubling the wait up to 5 seconds, with [jitter]. Other errors,
+    /// such as failed authentication, are returned immediately.
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPool::builder()
+    ///     .connect_retry(
+    ///         RetryPolicy::new()
+    ///             .max_attempts(10)
+    ///             .backoff(Duration::from_millis(100), Duration::from_secs(10))
+    ///             .jitter(true),
+    ///     )
+    ///     .build(&url)
+    ///     .await?;
+    /// ```
+    ///
+    /// [`connect_timeout`]: #method.connect_timeout
+    /// [jitter]: RetryPolicy::jitter
+    pub fn connect_retry(mut self, policy: RetryPolicy) -> Self {
+        self.options.connect_retry = policy;
+        self
+    }
+
     /// Set the minimum number of connections to maintain at all times.
     ///
     /// When the pool is built, this many connections will be automatically spun up.
@@ -107,17 +151,187 @@ where
         self
     }
 
+    /// Set the maximum number of prepared statements each connection caches.
+    ///
+    /// This overrides the `statement-cache-capacity` parameter of the connection URL.
+    /// See [`Connection::set_statement_cache_capacity`].
+    ///
+    /// [`Connection::set_statement_cache_capacity`]: crate::connection::Connection::set_statement_cache_capacity
+    pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
+        self.options.statement_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Set how queries executed on each connection are logged.
+    ///
+    /// See [`Connection::set_log_settings`].
+    ///
+    /// [`Connection::set_log_settings`]: crate::connection::Connection::set_log_settings
+    pub fn log_settings(mut self, settings: LogSettings) -> Self {
+        self.options.log_settings = Some(settings);
+        self
+    }
+
+    /// Set a hook that is called with each new connection, before it is first used.
+    ///
+    /// This is the place for per-connection setup, such as setting session variables or
+    /// registering functions. If the hook fails, the connection is closed and the error is
+    /// returned from [`Pool::acquire`].
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPool::builder()
+    ///     .after_connect(|conn| {
+    ///         Box::pin(async move {
+    ///             conn.execute("SET search_path = app").await?;
+    ///
+    ///             Ok(())
+    ///         })
+    ///     })
+    ///     .build(&url)
+    ///     .await?;
+    /// ```
+    ///
+    /// [`Pool::acquire`]: crate::pool::Pool::acquire
+    pub fn after_connect<F>(mut self, callback: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut C) -> BoxFuture<'c, crate::Result<()>> + Send + Sync + 'static,
+    {
+        self.options.after_connect = Some(Box::new(callback));
+        self
+    }
+
+    /// Set a hook that is called with an idle connection before it is returned from
+    /// [`Pool::acquire`], after it passed the check of [`test_on_acquire`].
+    ///
+    /// If the hook returns `Ok(false)` or an error, the connection is closed and another one is
+    /// acquired instead. This hook is not called by [`Pool::try_acquire`].
+    ///
+    /// [`Pool::acquire`]: crate::pool::Pool::acquire
+    /// [`Pool::try_acquire`]: crate::pool::Pool::try_acquire
+    /// [`test_on_acquire`]: #method.test_on_acquire
+    pub fn before_acquire<F>(mut self, callback: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut C) -> BoxFuture<'c, crate::Result<bool>> + Send + Sync + 'static,
+    {
+        self.options.before_acquire = Some(Box::new(callback));
+        self
+    }
+
+    /// Set a hook that is called with a connection after it is released, before it is returned
+    /// to the idle connections of the pool, e.g. to reset the state of its session.
+    ///
+    /// The hook runs in the background once the connection is dropped, after an open transaction
+    /// has been rolled back. If it returns `Ok(false)` or an error, the connection is closed
+    /// instead.
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPool::builder()
+    ///     .after_release(|conn| {
+    ///         Box::pin(async move {
+    ///             conn.execute("RESET ROLE").await?;
+    ///
+    ///             Ok(true)
+    ///         })
+    ///     })
+    ///     .build(&url)
+    ///     .await?;
+    /// ```
+    pub fn after_release<F>(mut self, callback: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut C) -> BoxFuture<'c, crate::Result<bool>> + Send + Sync + 'static,
+    {
+        self.options.after_release = Some(Box::new(callback));
+        self
+    }
+
+    /// Set a provider of the username and password for each new connection, e.g. to
+    /// authenticate with short-lived tokens.
+    ///
+    /// The provider is called before every connection is opened, and the credentials it
+    /// returns replace those of the connect options. If it fails, the error is handled as if
+    /// opening the connection failed. The provider should cache credentials that are still
+    /// valid, as the pool may open connections at any time.
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPool::builder()
+    ///     .credentials(move || {
+    ///         let tokens = tokens.clone();
+    ///
+    ///         Box::pin(async move {
+    ///             let token = tokens.current().await?;
+    ///
+    ///             Ok(Credentials::new().username("app").password(&token))
+    ///         })
+    ///     })
+    ///     .build("postgres://db.example.com/app")
+    ///     .await?;
+    /// ```
+    pub fn credentials<F>(mut self, provider: F) -> Self
+    where
+        F: Fn() -> BoxFuture<'static, crate::Result<Credentials>> + Send + Sync + 'static,
+    {
+        self.options.credentials = Some(Box::new(provider));
+        self
+    }
+
+    /// Set an observer that is notified of the events of the pool, such as connections being
+    /// acquired, opened and closed.
+    ///
+    /// The pool keeps its own counters regardless; see [`Pool::metrics`].
+    ///
+    /// [`Pool::metrics`]: crate::pool::Pool::metrics
+    pub fn observer<O>(mut self, observer: O) -> Self
+    where
+        O: PoolObserver,
+    {
+        self.options.observer = Some(Arc::new(observer));
+        self
+    }
+
     /// Spin up the connection pool.
     ///
     /// If [`min_size`] was set to a non-zero value, that many connections will be immediately
     /// opened and placed into the pool.
     ///
+    /// The URL is parsed into the [`Options`](Connect::Options) of the connection type; see
+    /// [`build_with`](Self::build_with).
+    ///
     /// [`min_size`]: #method.min_size
-    pub async fn build(self, url: &str) -> crate::Result<Pool<C>>
-    where
-        C: Connect,
-    {
-        Pool::<C>::with_options(url, self.options).await
+    pub async fn build(self, url: &str) -> crate::Result<Pool<C>> {
+        self.build_with(url.parse()?).await
+    }
+
+    /// Spin up the connection pool, establishing each connection with the given options.
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPool::builder()
+    ///     .max_size(5)
+    ///     .build_with(PgConnectOptions::new().host("localhost").password("p@ss/word"))
+    ///     .await?;
+    /// ```
+    pub async fn build_with(self, connect_options: C::Options) -> crate::Result<Pool<C>> {
+        Pool::<C>::with_options(connect_options, self.options).await
+    }
+
+    /// Create the connection pool without opening any connections.
+    ///
+    /// Connections are opened as they are acquired, so the database need not be up yet when
+    /// the pool is created. The [`min_size`] is not opened up front.
+    ///
+    /// Must be called from within the async runtime, as the pool reaps connections in
+    /// the background.
+    ///
+    /// [`min_size`]: #method.min_size
+    pub fn build_lazy(self, url: &str) -> crate::Result<Pool<C>> {
+        Ok(self.build_lazy_with(url.parse()?))
+    }
+
+    /// Create the connection pool without opening any connections, establishing each
+    /// connection with the given options once it is acquired.
+    ///
+    /// See [`build_lazy`](Self::build_lazy).
+    pub fn build_lazy_with(self, connect_options: C::Options) -> Pool<C> {
+        Pool::<C>::with_options_lazy(connect_options, self.options)
     }
 }
 
@@ -131,12 +345,40 @@ where
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct Options {
+pub(crate) struct Options<C> {
     pub max_size: u32,
     pub connect_timeout: Duration,
+    pub connect_retry: RetryPolicy,
     pub min_size: u32,
     pub max_lifetime: Option<Duration>,
     pub idle_timeout: Option<Duration>,
     pub test_on_acquire: bool,
+    pub statement_cache_capacity: Option<usize>,
+    pub log_settings: Option<LogSettings>,
+    pub after_connect: Option<Box<Hook<C, ()>>>,
+    pub before_acquire: Option<Box<Hook<C, bool>>>,
+    pub after_release: Option<Box<Hook<C, bool>>>,
+    pub credentials: Option<Box<CredentialProvider>>,
+    pub observer: Option<Arc<dyn PoolObserver>>,
+}
+
+impl<C> fmt::Debug for Options<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Options")
+            .field("max_size", &self.max_size)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("connect_retry", &self.connect_retry)
+            .field("min_size", &self.min_size)
+            .field("max_lifetime", &self.max_lifetime)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("test_on_acquire", &self.test_on_acquire)
+            .field("statement_cache_capacity", &self.statement_cache_capacity)
+            .field("log_settings", &self.log_settings)
+            .field("after_connect", &self.after_connect.is_some())
+            .field("before_acquire", &self.before_acquire.is_some())
+            .field("after_release", &self.after_release.is_some())
+            .field("credentials", &self.credentials.is_some())
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
 }
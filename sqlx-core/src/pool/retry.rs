@@ -0,0 +1,243 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::{fmt, sync::Arc, time::Duration};
+
+use crate::error::{Error, ErrorKind};
+
+// Decides whether a failed attempt is retried
+type RetryIf = dyn Fn(&Error) -> bool + Send + Sync;
+
+/// Decides when and how often [`Pool::transaction_with_retry`] retries a transaction, or a
+/// pool retries opening a connection (see [`Builder::connect_retry`]).
+///
+/// By default, a transaction is attempted at most 3 times and is retried if it failed with a
+/// [serialization failure] or [deadlock]. The wait before each retry starts at 10 milliseconds
+/// and doubles after each attempt, up to a second.
+///
+/// When opening a connection, transient errors are retried instead, unless set otherwise
+/// with [`retry_if`](RetryPolicy::retry_if).
+///
+/// ```rust,ignore
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .backoff(Duration::from_millis(50), Duration::from_secs(2));
+/// ```
+///
+/// [`Pool::transaction_with_retry`]: crate::pool::Pool::transaction_with_retry
+/// [`Builder::connect_retry`]: crate::pool::Builder::connect_retry
+/// [serialization failure]: crate::error::ErrorKind::SerializationFailure
+/// [deadlock]: crate::error::ErrorKind::Deadlock
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_if: Option<Arc<RetryIf>>,
+}
+
+impl RetryPolicy {
+    /// Creates the default policy.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            jitter: false,
+            retry_if: None,
+        }
+    }
+
+    /// Sets the maximum number of times the transaction is attempted, including the
+    /// first attempt.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the wait before the first retry, and the most to wait before any retry. The wait
+    /// doubles after each attempt.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sets whether each wait is randomized to between half and all of the backoff, so that
+    /// many clients that failed at once do not retry at once.
+    ///
+    /// Defaults to `false`.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets which errors are retried, instead of serialization failures and deadlocks (or
+    /// transient errors, when opening a connection).
+    pub fn retry_if<F>(mut self, retry_if: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.retry_if = Some(Arc::new(retry_if));
+        self
+    }
+
+    // The policy for opening connections, which keeps retrying until the connect timeout
+    pub(super) fn connect_default() -> Self {
+        Self::new()
+            .max_attempts(u32::MAX)
+            .backoff(Duration::from_millis(50), Duration::from_secs(5))
+            .jitter(true)
+    }
+
+    // Whether the transaction attempt that failed with `error` should be retried
+    pub(super) fn should_retry(&self, attempt: u32, error: &Error) -> bool {
+        self.should_retry_with(attempt, error, is_retryable)
+    }
+
+    // Whether the connect attempt that failed with `error` should be retried
+    pub(super) fn should_retry_connect(&self, attempt: u32, error: &Error) -> bool {
+        self.should_retry_with(attempt, error, is_transient)
+    }
+
+    fn should_retry_with(&self, attempt: u32, error: &Error, default: fn(&Error) -> bool) -> bool {
+        if attempt >= self.max_attempts {
+            return false;
+        }
+
+        match &self.retry_if {
+            Some(retry_if) => retry_if(error),
+            None => default(error),
+        }
+    }
+
+    // The time to wait after the given (1-based) failed attempt
+    pub(super) fn backoff_after(&self, attempt: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+
+        let backoff = self
+            .initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff));
+
+        if self.jitter {
+            backoff / 2 + backoff.mul_f64(random_fraction() / 2.0)
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("jitter", &self.jitter)
+            .field("retry_if", &self.retry_if.is_some())
+            .finish()
+    }
+}
+
+fn is_retryable(error: &Error) -> bool {
+    match error {
+        Error::Database(error) => matches!(
+            error.kind(),
+            ErrorKind::SerializationFailure | ErrorKind::Deadlock
+        ),
+
+        _ => false,
+    }
+}
+
+// Errors that are expected to go away on their own while a database starts up, fails over
+// or is overloaded; authentication and configuration errors are not
+fn is_transient(error: &Error) -> bool {
+    match error {
+        // e.g. connection refused, or the socket of the database does not exist yet
+        Error::Io(_) => true,
+
+        Error::Database(error) => match error.code() {
+            // [postgres] too_many_connections, admin_shutdown, crash_shutdown,
+            // cannot_connect_now
+            Some("53300") | Some("57P01") | Some("57P02") | Some("57P03") => true,
+
+            // connection exceptions, e.g. [mysql] ER_CON_COUNT_ERROR
+            Some(code) => code.starts_with("08"),
+
+            None => false,
+        },
+
+        _ => false,
+    }
+}
+
+// A number in `[0, 1)` that is random enough to spread out retries
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+
+    (bits >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+    use crate::error::Error;
+
+    #[test]
+    fn it_doubles_the_backoff_up_to_the_maximum() {
+        let policy =
+            RetryPolicy::new().backoff(Duration::from_millis(10), Duration::from_millis(50));
+
+        assert_eq!(policy.backoff_after(1), Duration::from_millis(10));
+        assert_eq!(policy.backoff_after(2), Duration::from_millis(20));
+        assert_eq!(policy.backoff_after(3), Duration::from_millis(40));
+        assert_eq!(policy.backoff_after(4), Duration::from_millis(50));
+        assert_eq!(policy.backoff_after(100), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn it_jitters_between_half_and_all_of_the_backoff() {
+        let policy = RetryPolicy::new()
+            .backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .jitter(true);
+
+        for _ in 0..100 {
+            let backoff = policy.backoff_after(2);
+
+            assert!(backoff >= Duration::from_millis(100));
+            assert!(backoff <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn it_retries_transient_connect_errors() {
+        let policy = RetryPolicy::connect_default();
+        let refused = io::Error::new(io::ErrorKind::ConnectionRefused, "connection refused");
+
+        assert!(policy.should_retry_connect(1, &Error::Io(refused)));
+        assert!(!policy.should_retry_connect(1, &Error::Configuration("bad".into())));
+        assert!(!policy.should_retry(1, &Error::Io(io::ErrorKind::Other.into())));
+    }
+
+    #[test]
+    fn it_stops_after_the_maximum_attempts() {
+        let policy = RetryPolicy::new()
+            .max_attempts(2)
+            .retry_if(|error| matches!(error, Error::RowNotFound));
+
+        assert!(policy.should_retry(1, &Error::RowNotFound));
+        assert!(!policy.should_retry(2, &Error::RowNotFound));
+        assert!(!policy.should_retry(1, &Error::PoolClosed));
+    }
+}
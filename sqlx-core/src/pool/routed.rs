@@ -0,0 +1,346 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use futures_util::future::join_all;
+
+use super::Pool;
+use crate::connection::{Connect, Connection};
+use crate::runtime::{sleep, spawn, timeout};
+
+/// How [`RoutedPool::reader`] picks a replica among the healthy ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicaSelection {
+    /// Each replica in turn.
+    ///
+    /// This is the default.
+    RoundRobin,
+
+    /// The replica with the fewest checked-out connections.
+    LeastBusy,
+}
+
+/// A primary connection pool with a number of replica pools, routing reads to the replicas
+/// and writes to the primary.
+///
+/// The [`writer`](RoutedPool::writer) is always the primary. The [`reader`](RoutedPool::reader)
+/// is one of the replicas that passed their last health check, or the primary if none did.
+/// Each replica is checked periodically by [pinging](Connection::ping) one of its idle
+/// connections; a replica with every connection checked out is considered healthy.
+///
+/// ```rust,ignore
+/// let pool = RoutedPool::builder(PgPool::new("postgres://primary/app").await?)
+///     .replica(PgPool::new("postgres://replica-1/app").await?)
+///     .replica(PgPool::new("postgres://replica-2/app").await?)
+///     .selection(ReplicaSelection::LeastBusy)
+///     .build();
+///
+/// let users = sqlx::query("SELECT * FROM users").fetch_all(pool.reader()).await?;
+///
+/// sqlx::query("DELETE FROM sessions").execute(pool.writer()).await?;
+/// ```
+pub struct RoutedPool<C>(Arc<Routed<C>>)
+where
+    C: Connect;
+
+struct Routed<C>
+where
+    C: Connect,
+{
+    primary: Pool<C>,
+    replicas: Vec<Replica<C>>,
+    selection: ReplicaSelection,
+    health_check_timeout: Duration,
+
+    // The replica to start from in the next round-robin selection
+    next_replica: AtomicUsize,
+}
+
+struct Replica<C>
+where
+    C: Connect,
+{
+    pool: Pool<C>,
+    is_healthy: AtomicBool,
+}
+
+/// Builder for [RoutedPool].
+pub struct RoutedPoolBuilder<C>
+where
+    C: Connect,
+{
+    primary: Pool<C>,
+    replicas: Vec<Pool<C>>,
+    selection: ReplicaSelection,
+    health_check_interval: Option<Duration>,
+    health_check_timeout: Duration,
+}
+
+impl<C> RoutedPool<C>
+where
+    C: Connect,
+{
+    /// Creates a routed pool of the primary and replica pools, with the default configuration.
+    ///
+    /// Must be called from within the async runtime, as the health checks run in the
+    /// background.
+    pub fn new(primary: Pool<C>, replicas: Vec<Pool<C>>) -> Self {
+        Self::builder(primary).replicas(replicas).build()
+    }
+
+    /// Returns a [RoutedPoolBuilder] to configure a new routed pool of the primary pool.
+    pub fn builder(primary: Pool<C>) -> RoutedPoolBuilder<C> {
+        RoutedPoolBuilder {
+            primary,
+            replicas: Vec::new(),
+            selection: ReplicaSelection::RoundRobin,
+            health_check_interval: Some(Duration::from_secs(10)),
+            health_check_timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// The pool to execute writes on, which is the primary.
+    pub fn writer(&self) -> &Pool<C> {
+        &self.0.primary
+    }
+
+    /// The pool to execute reads on: a healthy replica, or the primary if there are no healthy
+    /// replicas.
+    ///
+    /// Each call may return a different replica.
+    pub fn reader(&self) -> &Pool<C> {
+        let replicas = &self.0.replicas;
+
+        let replica = match self.0.selection {
+            ReplicaSelection::RoundRobin => {
+                let start = self.0.next_replica.fetch_add(1, Ordering::Relaxed);
+
+                (0..replicas.len())
+                    .map(|i| &replicas[(start + i) % replicas.len()])
+                    .find(|replica| replica.is_healthy())
+            }
+
+            ReplicaSelection::LeastBusy => replicas
+                .iter()
+                .filter(|replica| replica.is_healthy())
+                .min_by_key(|replica| replica.checked_out()),
+        };
+
+        replica.map_or(&self.0.primary, |replica| &replica.pool)
+    }
+
+    /// Returns the number of replicas that passed their last health check.
+    pub fn healthy_replicas(&self) -> usize {
+        self.0
+            .replicas
+            .iter()
+            .filter(|replica| replica.is_healthy())
+            .count()
+    }
+
+    /// Checks the health of every replica now, instead of waiting for the next periodic check.
+    pub async fn check_health(&self) {
+        self.0.check_health().await;
+    }
+
+    /// Closes the primary and every replica pool.
+    ///
+    /// See [`Pool::close`].
+    pub async fn close(&self) {
+        for replica in &self.0.replicas {
+            replica.pool.close().await;
+        }
+
+        self.0.primary.close().await;
+    }
+}
+
+impl<C> Routed<C>
+where
+    C: Connect,
+{
+    // The replicas are checked concurrently, so that a replica that hangs does not delay the
+    // checks of the others
+    async fn check_health(&self) {
+        join_all(
+            self.replicas
+                .iter()
+                .map(|replica| self.check_replica(replica)),
+        )
+        .await;
+    }
+
+    async fn check_replica(&self, replica: &Replica<C>) {
+        let is_healthy = match timeout(self.health_check_timeout, replica.ping()).await {
+            Ok(Ok(())) => true,
+
+            Ok(Err(error)) => {
+                log::debug!("health check of a replica failed: {}", error);
+                false
+            }
+
+            Err(_) => {
+                log::debug!("health check of a replica timed out");
+                false
+            }
+        };
+
+        let was_healthy = replica.is_healthy.swap(is_healthy, Ordering::AcqRel);
+
+        if was_healthy && !is_healthy {
+            log::warn!("replica failed its health check and will not be read from");
+        } else if !was_healthy && is_healthy {
+            log::info!("replica passed its health check and will be read from again");
+        }
+    }
+}
+
+impl<C> Replica<C>
+where
+    C: Connect,
+{
+    fn is_healthy(&self) -> bool {
+        !self.pool.is_closed() && self.is_healthy.load(Ordering::Acquire)
+    }
+
+    fn checked_out(&self) -> usize {
+        (self.pool.size() as usize).saturating_sub(self.pool.idle())
+    }
+
+    // Pings an idle connection, so that the check does not wait in line behind the readers
+    async fn ping(&self) -> crate::Result<()> {
+        match self.pool.try_acquire() {
+            Some(mut conn) => conn.ping().await,
+
+            // Every connection is checked out, so the replica is busy serving reads
+            None if self.pool.size() >= self.pool.max_size() => Ok(()),
+
+            // There is room to open a new connection
+            None => self.pool.acquire().await?.ping().await,
+        }
+    }
+}
+
+impl<C> RoutedPoolBuilder<C>
+where
+    C: Connect,
+{
+    /// Adds a replica pool to route reads to.
+    pub fn replica(mut self, pool: Pool<C>) -> Self {
+        self.replicas.push(pool);
+        self
+    }
+
+    /// Adds replica pools to route reads to.
+    pub fn replicas(mut self, pools: impl IntoIterator<Item = Pool<C>>) -> Self {
+        self.replicas.extend(pools);
+        self
+    }
+
+    /// Sets how a replica is picked for each read.
+    ///
+    /// Defaults to [`ReplicaSelection::RoundRobin`].
+    pub fn selection(mut self, selection: ReplicaSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Sets how often the health of the replicas is checked.
+    ///
+    /// When set to `None`, the replicas are only checked by [`RoutedPool::check_health`].
+    /// Defaults to 10 seconds.
+    pub fn health_check_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+        self.health_check_interval = interval.into();
+        self
+    }
+
+    /// Sets how long a replica may take to hand out and ping a connection before it fails its
+    /// health check.
+    ///
+    /// Defaults to 5 seconds.
+    pub fn health_check_timeout(mut self, timeout: Duration) -> Self {
+        self.health_check_timeout = timeout;
+        self
+    }
+
+    /// Creates the routed pool, starting the periodic health checks.
+    ///
+    /// Every replica is considered healthy until it fails a health check.
+    pub fn build(self) -> RoutedPool<C> {
+        let routed = Arc::new(Routed {
+            primary: self.primary,
+            replicas: self
+                .replicas
+                .into_iter()
+                .map(|pool| Replica {
+                    pool,
+                    is_healthy: AtomicBool::new(true),
+                })
+                .collect(),
+            selection: self.selection,
+            health_check_timeout: self.health_check_timeout,
+            next_replica: AtomicUsize::new(0),
+        });
+
+        if let Some(interval) = self.health_check_interval {
+            if !routed.replicas.is_empty() {
+                spawn_health_checks(Arc::downgrade(&routed), interval);
+            }
+        }
+
+        RoutedPool(routed)
+    }
+}
+
+// The health checks stop once every handle to the routed pool is dropped, or its primary
+// is closed
+fn spawn_health_checks<C>(routed: Weak<Routed<C>>, interval: Duration)
+where
+    C: Connect,
+{
+    spawn(async move {
+        loop {
+            sleep(interval).await;
+
+            let routed = match routed.upgrade() {
+                Some(routed) if !routed.primary.is_closed() => routed,
+                _ => break,
+            };
+
+            routed.check_health().await;
+        }
+    });
+}
+
+/// Returns a new [RoutedPool] tied to the same primary and replica pools.
+impl<C> Clone for RoutedPool<C>
+where
+    C: Connect,
+{
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<C> fmt::Debug for RoutedPool<C>
+where
+    C: Connect,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("RoutedPool")
+            .field("primary", &self.0.primary)
+            .field(
+                "replicas",
+                &self
+                    .0
+                    .replicas
+                    .iter()
+                    .map(|replica| &replica.pool)
+                    .collect::<Vec<_>>(),
+            )
+            .field("healthy_replicas", &self.healthy_replicas())
+            .field("selection", &self.0.selection)
+            .finish()
+    }
+}
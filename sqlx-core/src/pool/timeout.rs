@@ -0,0 +1,76 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::time::Duration;
+
+/// The state of a pool when acquiring a connection from it timed out.
+///
+/// This is the source of the [`Error::PoolTimedOut`](crate::error::Error::PoolTimedOut)
+/// returned from [`Pool::acquire`](crate::pool::Pool::acquire), and tells whether the pool
+/// was exhausted by checked-out connections or could not open new ones.
+///
+/// ```rust,ignore
+/// if let Err(sqlx::Error::PoolTimedOut(Some(source))) = pool.acquire().await {
+///     if let Some(timeout) = source.downcast_ref::<PoolTimeout>() {
+///         println!("{} connections are checked out", timeout.size() as usize - timeout.idle());
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PoolTimeout {
+    pub(super) size: u32,
+    pub(super) max_size: u32,
+    pub(super) idle: usize,
+    pub(super) waiters: usize,
+    pub(super) elapsed: Duration,
+    pub(super) last_connect_error: Option<String>,
+}
+
+impl PoolTimeout {
+    /// The number of connections managed by the pool, idle or checked out.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// The maximum size of the pool.
+    pub fn max_size(&self) -> u32 {
+        self.max_size
+    }
+
+    /// The number of idle connections.
+    pub fn idle(&self) -> usize {
+        self.idle
+    }
+
+    /// The number of tasks waiting for a connection, including the one that timed out.
+    pub fn waiters(&self) -> usize {
+        self.waiters
+    }
+
+    /// The time spent waiting for a connection.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The message of the last error from opening a connection while waiting, if any.
+    pub fn last_connect_error(&self) -> Option<&str> {
+        self.last_connect_error.as_deref()
+    }
+}
+
+impl fmt::Display for PoolTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "waited {:?} with {} of {} connections open, {} idle and {} tasks waiting",
+            self.elapsed, self.size, self.max_size, self.idle, self.waiters
+        )?;
+
+        if let Some(error) = &self.last_connect_error {
+            write!(f, "; the last connect attempt failed with: {}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StdError for PoolTimeout {}
@@ -0,0 +1,112 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use super::connection::Idle;
+
+/// What the pool hands to a task waiting for a connection.
+pub(super) enum Handoff<C> {
+    /// A connection released by another task.
+    Idle(Idle<C>),
+
+    /// A slot freed by a closed connection; the size of the pool already counts the new
+    /// connection the waiter is to open.
+    Permit,
+}
+
+/// A task waiting in [`Pool::acquire`](crate::pool::Pool::acquire).
+pub(super) struct Waiter<C> {
+    state: Mutex<WaiterState<C>>,
+}
+
+struct WaiterState<C> {
+    handoff: Option<Handoff<C>>,
+    waker: Option<Waker>,
+}
+
+impl<C> Waiter<C> {
+    pub(super) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(WaiterState {
+                handoff: None,
+                waker: None,
+            }),
+        })
+    }
+
+    /// Gives the waiter a connection (or permit) and wakes it.
+    pub(super) fn hand(&self, handoff: Handoff<C>) {
+        let mut state = lock(&self.state);
+
+        state.handoff = Some(handoff);
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Wakes the waiter without handing it anything, e.g. when the pool is closed.
+    pub(super) fn wake(&self) {
+        if let Some(waker) = lock(&self.state).waker.take() {
+            waker.wake();
+        }
+    }
+
+    pub(super) fn poll_handoff(&self, cx: &mut Context<'_>) -> Poll<Handoff<C>> {
+        let mut state = lock(&self.state);
+
+        match state.handoff.take() {
+            Some(handoff) => Poll::Ready(handoff),
+
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    pub(super) fn take_handoff(&self) -> Option<Handoff<C>> {
+        lock(&self.state).handoff.take()
+    }
+}
+
+/// The tasks waiting for a connection, oldest first.
+pub(super) struct WaiterQueue<C> {
+    waiters: Mutex<VecDeque<Arc<Waiter<C>>>>,
+}
+
+impl<C> WaiterQueue<C> {
+    pub(super) fn new() -> Self {
+        Self {
+            waiters: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Locks the queue. Connections and permits are only handed out, and waiters only
+    /// enqueued, while it is locked, so that a waiter cannot miss a connection released
+    /// just before it enqueued itself.
+    pub(super) fn lock(&self) -> MutexGuard<'_, VecDeque<Arc<Waiter<C>>>> {
+        lock(&self.waiters)
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Removes a waiter that stopped waiting from the queue.
+    pub(super) fn remove(&self, waiter: &Arc<Waiter<C>>) {
+        let mut waiters = self.lock();
+
+        if let Some(index) = waiters
+            .iter()
+            .position(|queued| Arc::ptr_eq(queued, waiter))
+        {
+            waiters.remove(index);
+        }
+    }
+}
+
+// A panic while a lock is held leaves the queue consistent, so poisoning is ignored
+pub(super) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
@@ -3,6 +3,7 @@ use byteorder::{ByteOrder, NetworkEndian};
 use crate::arguments::Arguments;
 use crate::encode::{Encode, IsNull};
 use crate::io::BufMut;
+use crate::postgres::protocol::TypeId;
 use crate::postgres::{PgRawBuffer, PgTypeInfo, Postgres};
 use crate::types::Type;
 
@@ -15,6 +16,61 @@ pub struct PgArguments {
     pub(super) buffer: PgRawBuffer,
 }
 
+impl PgArguments {
+    // Renders each bound value for the query log
+    pub(crate) fn log_values(&self) -> Vec<String> {
+        let mut buf = &self.buffer[..];
+        let mut values = Vec::with_capacity(self.types.len());
+
+        for ty in &self.types {
+            if buf.len() < 4 {
+                break;
+            }
+
+            let len = NetworkEndian::read_i32(buf);
+            buf = &buf[4..];
+
+            if len < 0 {
+                values.push("NULL".to_owned());
+                continue;
+            }
+
+            let len = len as usize;
+
+            if buf.len() < len {
+                break;
+            }
+
+            values.push(log_value(ty, &buf[..len]));
+            buf = &buf[len..];
+        }
+
+        values
+    }
+}
+
+fn log_value(ty: &PgTypeInfo, value: &[u8]) -> String {
+    match ty.id {
+        Some(TypeId::BOOL) if value.len() == 1 => (value[0] != 0).to_string(),
+        Some(TypeId::INT2) if value.len() == 2 => NetworkEndian::read_i16(value).to_string(),
+        Some(TypeId::INT4) if value.len() == 4 => NetworkEndian::read_i32(value).to_string(),
+        Some(TypeId::OID) if value.len() == 4 => NetworkEndian::read_u32(value).to_string(),
+        Some(TypeId::INT8) if value.len() == 8 => NetworkEndian::read_i64(value).to_string(),
+        Some(TypeId::FLOAT4) if value.len() == 4 => NetworkEndian::read_f32(value).to_string(),
+        Some(TypeId::FLOAT8) if value.len() == 8 => NetworkEndian::read_f64(value).to_string(),
+
+        Some(TypeId::TEXT)
+        | Some(TypeId::VARCHAR)
+        | Some(TypeId::BPCHAR)
+        | Some(TypeId::NAME)
+        | Some(TypeId::UNKNOWN) => format!("{:?}", String::from_utf8_lossy(value)),
+
+        Some(TypeId::BYTEA) => format!("'\\x{}'", hex::encode(value)),
+
+        _ => format!("<{}>", ty),
+    }
+}
+
 impl Arguments for PgArguments {
     type Database = super::Postgres;
 
@@ -1,23 +1,29 @@
-use std::borrow::Cow;
 use std::collections::HashMap;
-use std::convert::TryInto;
+use std::io;
 use std::sync::Arc;
 
 use futures_core::future::BoxFuture;
 use futures_util::TryFutureExt;
 
+use crate::cache::StatementCache;
 use crate::connection::{Connect, Connection};
 use crate::executor::Executor;
+use crate::logging::LogSettings;
+use crate::pool::Credentials;
 
 use crate::postgres::protocol::{
     Authentication, AuthenticationMd5, AuthenticationSasl, BackendKeyData, Message,
-    PasswordMessage, StartupMessage, StatementId, Terminate,
+    PasswordMessage, StartupMessage, StatementId, Terminate, TransactionStatus,
 };
 use crate::postgres::row::Statement;
 use crate::postgres::stream::PgStream;
 use crate::postgres::type_info::SharedStr;
-use crate::postgres::{sasl, tls};
-use crate::url::Url;
+use crate::postgres::{
+    pgpass, sasl, tls, PgConnectOptions, PgQueryAs, PgTargetSessionAttrs, Postgres,
+};
+use crate::query_as::query_as;
+use crate::statement::{next_connection_id, Statement as PreparedStatement};
+use crate::transaction::TransactionOptions;
 
 /// An asynchronous connection to a [Postgres](struct.Postgres.html) database.
 ///
@@ -25,6 +31,9 @@ use crate::url::Url;
 /// string, as documented at
 /// <https://www.postgresql.org/docs/12/libpq-connect.html#LIBPQ-CONNSTRING>
 ///
+/// The same settings can be built programmatically with [`PgConnectOptions`] and passed to
+/// [Connect::connect_with].
+///
 /// ### TLS Support (requires `tls` feature)
 /// This connection type supports the same `sslmode` query parameter that `libpq` does in
 /// connection strings: <https://www.postgresql.org/docs/12/libpq-ssl.html>
@@ -80,12 +89,16 @@ use crate::url::Url;
 /// against the hostname in the server certificate, so they must be the same for the TLS
 /// upgrade to succeed.
 pub struct PgConnection {
+    pub(crate) id: u64,
     pub(super) stream: PgStream,
     pub(super) next_statement_id: u32,
     pub(super) is_ready: bool,
 
+    // transaction status reported by the last [ReadyForQuery]
+    pub(super) transaction_status: TransactionStatus,
+
     // cache query -> statement ID
-    pub(super) cache_statement_id: HashMap<Box<str>, StatementId>,
+    pub(super) cache_statement_id: StatementCache<StatementId>,
 
     // cache statement ID -> statement description
     pub(super) cache_statement: HashMap<StatementId, Arc<Statement>>,
@@ -96,6 +109,8 @@ pub struct PgConnection {
     // cache type OID -> type name
     pub(super) cache_type_name: HashMap<u32, SharedStr>,
 
+    pub(super) log_settings: LogSettings,
+
     // Work buffer for the value ranges of the current row
     // This is used as the backing memory for each Row's value indexes
     pub(super) current_row_values: Vec<Option<(u32, u32)>>,
@@ -109,19 +124,18 @@ pub struct PgConnection {
 }
 
 // https://www.postgresql.org/docs/12/protocol-flow.html#id-1.10.5.7.3
-async fn startup(stream: &mut PgStream, url: &Url) -> crate::Result<BackendKeyData> {
-    // Defaults to $USER@.../$USER
-    // and falls back to postgres@.../postgres
-    let username = url
-        .username()
-        .or_else(|| std::env::var("USER").map(Cow::Owned).ok())
-        .unwrap_or(Cow::Borrowed("postgres"));
-    let database = url.database().unwrap_or(&username);
+async fn startup(
+    stream: &mut PgStream,
+    options: &PgConnectOptions,
+    password: &str,
+) -> crate::Result<BackendKeyData> {
+    let username = &*options.username;
+    let database = options.database_or_username();
 
     // See this doc for more runtime parameters
     // https://www.postgresql.org/docs/12/runtime-config-client.html
-    let params = &[
-        ("user", username.as_ref()),
+    let mut params = vec![
+        ("user", username),
         ("database", database),
         // Sets the display format for date and time values,
         // as well as the rules for interpreting ambiguous date input values.
@@ -135,7 +149,9 @@ async fn startup(stream: &mut PgStream, url: &Url) -> crate::Result<BackendKeyDa
         ("client_encoding", "UTF-8"),
     ];
 
-    stream.write(StartupMessage { params });
+    params.extend(options.startup_params());
+
+    stream.write(StartupMessage { params: &params });
     stream.flush().await?;
 
     let mut key_data = BackendKeyData {
@@ -151,9 +167,7 @@ async fn startup(stream: &mut PgStream, url: &Url) -> crate::Result<BackendKeyDa
                 }
 
                 Authentication::CleartextPassword => {
-                    stream.write(PasswordMessage::ClearText(
-                        &url.password().unwrap_or_default(),
-                    ));
+                    stream.write(PasswordMessage::ClearText(password));
 
                     stream.flush().await?;
                 }
@@ -164,8 +178,8 @@ async fn startup(stream: &mut PgStream, url: &Url) -> crate::Result<BackendKeyDa
                     let data = AuthenticationMd5::read(&stream.buffer()[4..])?;
 
                     stream.write(PasswordMessage::Md5 {
-                        password: &url.password().unwrap_or_default(),
-                        user: username.as_ref(),
+                        password,
+                        user: username,
                         salt: data.salt,
                     });
 
@@ -198,12 +212,7 @@ async fn startup(stream: &mut PgStream, url: &Url) -> crate::Result<BackendKeyDa
 
                     if has_sasl || has_sasl_plus {
                         // TODO: Handle -PLUS differently if we're in a TLS stream
-                        sasl::authenticate(
-                            stream,
-                            username.as_ref(),
-                            &url.password().unwrap_or_default(),
-                        )
-                        .await?;
+                        sasl::authenticate(stream, username, password).await?;
                     } else {
                         return Err(protocol_err!(
                             "unsupported SASL auth mechanisms: {:?}",
@@ -253,36 +262,152 @@ async fn terminate(mut stream: PgStream) -> crate::Result<()> {
     Ok(())
 }
 
+// https://www.postgresql.org/docs/12/libpq-connect.html#LIBPQ-MULTIPLE-HOSTS
+async fn connect(options: &PgConnectOptions) -> crate::Result<PgConnection> {
+    let targets = options.targets()?;
+
+    // `prefer-standby` looks for a standby first, and then accepts any server
+    let passes = match options.target_session_attrs {
+        PgTargetSessionAttrs::PreferStandby => {
+            vec![PgTargetSessionAttrs::Standby, PgTargetSessionAttrs::Any]
+        }
+
+        attrs => vec![attrs],
+    };
+
+    let mut last_error = None;
+
+    for attrs in passes {
+        for target in &targets {
+            match connect_matching(target, attrs).await {
+                Ok(conn) => return Ok(conn),
+
+                Err(error) => {
+                    if targets.len() > 1 {
+                        log::info!("skipping {}: {}", target.display_host(), error);
+                    }
+
+                    last_error = Some(error);
+                }
+            }
+        }
+    }
+
+    Err(last_error.expect("BUG: no hosts were tried"))
+}
+
+// Connects to a single host, and checks that its server is the kind of server required
+async fn connect_matching(
+    options: &PgConnectOptions,
+    attrs: PgTargetSessionAttrs,
+) -> crate::Result<PgConnection> {
+    let mut conn = connect_host(options).await?;
+
+    if !conn.matches_session_attrs(attrs).await? {
+        let _ = conn.close().await;
+
+        // Reported as an IO error, so that a pool keeps trying while the servers fail over
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "server at {} does not match target_session_attrs {:?}",
+                options.display_host(),
+                attrs
+            ),
+        )
+        .into());
+    }
+
+    conn.stream.requires_writes = matches!(
+        attrs,
+        PgTargetSessionAttrs::ReadWrite | PgTargetSessionAttrs::Primary
+    );
+
+    Ok(conn)
+}
+
+async fn connect_host(options: &PgConnectOptions) -> crate::Result<PgConnection> {
+    let mut stream = PgStream::new(options).await?;
+
+    tls::request_if_needed(&mut stream, options).await?;
+
+    let password = match &options.password {
+        Some(password) => Some(password.clone()),
+        None => pgpass::load_password(options).await,
+    };
+
+    let key_data = startup(
+        &mut stream,
+        options,
+        password.as_deref().unwrap_or_default(),
+    )
+    .await?;
+
+    Ok(PgConnection {
+        id: next_connection_id(),
+        stream,
+        current_row_values: Vec::with_capacity(10),
+        next_statement_id: 1,
+        is_ready: true,
+        transaction_status: TransactionStatus::Idle,
+        cache_type_oid: HashMap::new(),
+        cache_type_name: HashMap::new(),
+        cache_statement_id: StatementCache::new(options.statement_cache_capacity),
+        cache_statement: HashMap::with_capacity(10),
+        log_settings: options.log_settings.clone(),
+        process_id: key_data.process_id,
+        secret_key: key_data.secret_key,
+    })
+}
+
 impl PgConnection {
-    pub(super) async fn new(url: std::result::Result<Url, url::ParseError>) -> crate::Result<Self> {
-        let url = url?;
-        let mut stream = PgStream::new(&url).await?;
-
-        tls::request_if_needed(&mut stream, &url).await?;
-        let key_data = startup(&mut stream, &url).await?;
-
-        Ok(Self {
-            stream,
-            current_row_values: Vec::with_capacity(10),
-            next_statement_id: 1,
-            is_ready: true,
-            cache_type_oid: HashMap::new(),
-            cache_type_name: HashMap::new(),
-            cache_statement_id: HashMap::with_capacity(10),
-            cache_statement: HashMap::with_capacity(10),
-            process_id: key_data.process_id,
-            secret_key: key_data.secret_key,
+    // Whether the server of the connection is the kind of server the session is required
+    // to be on
+    async fn matches_session_attrs(&mut self, attrs: PgTargetSessionAttrs) -> crate::Result<bool> {
+        if let PgTargetSessionAttrs::Any = attrs {
+            return Ok(true);
+        }
+
+        // language=SQL
+        let (in_recovery, read_only): (bool, String) =
+            query_as("SELECT pg_is_in_recovery(), current_setting('transaction_read_only')")
+                .fetch_one(&mut *self)
+                .await?;
+
+        let read_only = read_only == "on";
+
+        Ok(match attrs {
+            PgTargetSessionAttrs::ReadWrite => !read_only,
+            PgTargetSessionAttrs::ReadOnly => read_only,
+            PgTargetSessionAttrs::Primary => !in_recovery,
+            PgTargetSessionAttrs::Standby => in_recovery,
+            PgTargetSessionAttrs::Any | PgTargetSessionAttrs::PreferStandby => true,
         })
     }
 }
 
 impl Connect for PgConnection {
-    fn connect<T>(url: T) -> BoxFuture<'static, crate::Result<PgConnection>>
-    where
-        T: TryInto<Url, Error = url::ParseError>,
-        Self: Sized,
-    {
-        Box::pin(PgConnection::new(url.try_into()))
+    type Options = PgConnectOptions;
+
+    fn connect_with(options: &PgConnectOptions) -> BoxFuture<'_, crate::Result<Self>> {
+        Box::pin(connect(options))
+    }
+
+    fn with_credentials(
+        options: &PgConnectOptions,
+        credentials: &Credentials,
+    ) -> crate::Result<PgConnectOptions> {
+        let mut options = options.clone();
+
+        if let Some(username) = &credentials.username {
+            options = options.username(username);
+        }
+
+        if let Some(password) = &credentials.password {
+            options = options.password(password);
+        }
+
+        Ok(options)
     }
 }
 
@@ -294,4 +419,60 @@ impl Connection for PgConnection {
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         Box::pin(Executor::execute(self, "SELECT 1").map_ok(|_| ()))
     }
+
+    fn cached_statements_size(&self) -> usize {
+        self.cache_statement_id.len()
+    }
+
+    fn set_statement_cache_capacity(&mut self, capacity: usize) {
+        self.cache_statement_id.set_capacity(capacity);
+    }
+
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>> {
+        Box::pin(self.clear_statement_cache())
+    }
+
+    fn log_settings(&self) -> &LogSettings {
+        &self.log_settings
+    }
+
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        self.log_settings = settings;
+    }
+
+    fn prepare<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, crate::Result<PreparedStatement<Postgres>>> {
+        Box::pin(self.prepare_statement(sql))
+    }
+
+    fn is_broken(&self) -> bool {
+        self.stream.is_broken
+    }
+
+    fn in_transaction(&self) -> bool {
+        // A failed transaction is still open until it is rolled back
+        self.transaction_status != TransactionStatus::Idle
+    }
+
+    #[doc(hidden)]
+    fn begin_statements(&self, options: &TransactionOptions) -> Vec<String> {
+        let mut statement = String::from("BEGIN");
+
+        if let Some(isolation) = options.isolation {
+            statement.push_str(" ISOLATION LEVEL ");
+            statement.push_str(isolation.as_sql());
+        }
+
+        if options.read_only {
+            statement.push_str(" READ ONLY");
+        }
+
+        if options.deferrable {
+            statement.push_str(" DEFERRABLE");
+        }
+
+        vec![statement]
+    }
 }
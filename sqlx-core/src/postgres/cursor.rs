@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use futures_core::future::BoxFuture;
@@ -5,17 +6,52 @@ use futures_core::future::BoxFuture;
 use crate::connection::ConnectionSource;
 use crate::cursor::Cursor;
 use crate::executor::Execute;
+use crate::logging::QueryLogger;
 use crate::pool::Pool;
-use crate::postgres::protocol::{DataRow, Message, ReadyForQuery, RowDescription};
+use crate::postgres::protocol::{CommandComplete, DataRow, Message, ReadyForQuery, RowDescription};
 use crate::postgres::row::Statement;
 use crate::postgres::{PgArguments, PgConnection, PgRow, Postgres};
+use crate::statement::check_connection;
 
 pub struct PgCursor<'c, 'q> {
     source: ConnectionSource<'c, PgConnection>,
-    query: Option<(&'q str, Option<PgArguments>)>,
+    state: PgCursorState<'q>,
+}
+
+// The progress of a cursor through the result of its query, kept apart from the connection so
+// that the `Any` driver can read it from a connection it holds itself
+pub(crate) struct PgCursorState<'q> {
+    query: Option<(Cow<'q, str>, Option<PgArguments>)>,
+    connection_id: Option<u64>,
+    logger: Option<QueryLogger<'q>>,
     statement: Arc<Statement>,
 }
 
+impl<'q> PgCursorState<'q> {
+    pub(crate) fn new(
+        query: Cow<'q, str>,
+        arguments: Option<PgArguments>,
+        connection_id: Option<u64>,
+    ) -> Self {
+        Self {
+            query: Some((query, arguments)),
+            connection_id,
+            logger: None,
+            statement: Arc::default(),
+        }
+    }
+
+    fn from_query<E>(query: E) -> Self
+    where
+        E: Execute<'q, Postgres>,
+    {
+        let connection_id = query.connection_id();
+        let (query, arguments) = query.into_parts();
+
+        Self::new(Cow::Borrowed(query), arguments, connection_id)
+    }
+}
+
 impl crate::cursor::private::Sealed for PgCursor<'_, '_> {}
 
 impl<'c, 'q> Cursor<'c, 'q> for PgCursor<'c, 'q> {
@@ -29,8 +65,7 @@ impl<'c, 'q> Cursor<'c, 'q> for PgCursor<'c, 'q> {
     {
         Self {
             source: ConnectionSource::Pool(pool.clone()),
-            statement: Arc::default(),
-            query: Some(query.into_parts()),
+            state: PgCursorState::from_query(query),
         }
     }
 
@@ -42,8 +77,7 @@ impl<'c, 'q> Cursor<'c, 'q> for PgCursor<'c, 'q> {
     {
         Self {
             source: ConnectionSource::ConnectionRef(conn),
-            statement: Arc::default(),
-            query: Some(query.into_parts()),
+            state: PgCursorState::from_query(query),
         }
     }
 
@@ -55,13 +89,30 @@ impl<'c, 'q> Cursor<'c, 'q> for PgCursor<'c, 'q> {
 async fn next<'a, 'c: 'a, 'q: 'a>(
     cursor: &'a mut PgCursor<'c, 'q>,
 ) -> crate::Result<Option<PgRow<'a>>> {
-    let mut conn = cursor.source.resolve().await?;
+    let conn = cursor.source.resolve().await?;
+
+    next_row(conn, &mut cursor.state).await
+}
 
+pub(crate) async fn next_row<'a, 'q: 'a>(
+    conn: &'a mut PgConnection,
+    cursor: &'a mut PgCursorState<'q>,
+) -> crate::Result<Option<PgRow<'a>>> {
     // The first time [next] is called we need to actually execute our
     // contained query. We guard against this happening on _all_ next calls
     // by using [Option::take] which replaces the potential value in the Option with `None
     if let Some((query, arguments)) = cursor.query.take() {
-        let statement = conn.run(query, arguments).await?;
+        check_connection(cursor.connection_id, conn.id)?;
+
+        let mut logger = QueryLogger::new(query.clone(), &conn.log_settings);
+
+        if let Some(arguments) = &arguments {
+            logger.arguments(|| arguments.log_values());
+        }
+
+        cursor.logger = Some(logger);
+
+        let statement = conn.run(&query, arguments).await?;
 
         // If there is a statement ID, this is a non-simple or prepared query
         if let Some(statement) = statement {
@@ -77,17 +128,27 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
         match conn.stream.receive().await? {
             // Indicates that a phase of the extended query flow has completed
             // We as SQLx don't generally care as long as it is happening
-            Message::ParseComplete | Message::BindComplete => {}
+            Message::ParseComplete | Message::BindComplete | Message::CloseComplete => {}
 
             // Indicates that _a_ query has finished executing
-            Message::CommandComplete => {}
+            Message::CommandComplete => {
+                if let Some(logger) = &mut cursor.logger {
+                    let rows = CommandComplete::read(conn.stream.buffer())?.affected_rows;
+                    logger.add_rows_affected(rows);
+                }
+            }
 
             // Indicates that all queries have finished executing
             Message::ReadyForQuery => {
                 // TODO: How should we handle an ERROR status form ReadyForQuery
-                let _ready = ReadyForQuery::read(conn.stream.buffer())?;
+                let ready = ReadyForQuery::read(conn.stream.buffer())?;
 
+                conn.transaction_status = ready.status();
                 conn.is_ready = true;
+
+                // The query is logged once its result has been consumed
+                cursor.logger = None;
+
                 break;
             }
 
@@ -103,6 +164,10 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
             Message::DataRow => {
                 let data = DataRow::read(conn.stream.buffer(), &mut conn.current_row_values)?;
 
+                if let Some(logger) = &mut cursor.logger {
+                    logger.increment_rows_returned();
+                }
+
                 return Ok(Some(PgRow {
                     statement: Arc::clone(&cursor.statement),
                     data,
@@ -1,7 +1,7 @@
 use std::error::Error as StdError;
 use std::fmt::{self, Display};
 
-use crate::error::DatabaseError;
+use crate::error::{DatabaseError, ErrorKind};
 use crate::postgres::protocol::Response;
 
 #[derive(Debug)]
@@ -36,6 +36,21 @@ impl DatabaseError for PgError {
         self.0.constraint.as_ref().map(|s| &**s)
     }
 
+    fn kind(&self) -> ErrorKind {
+        // https://www.postgresql.org/docs/current/errcodes-appendix.html
+        match &*self.0.code {
+            "23505" => ErrorKind::UniqueViolation,
+            "23503" => ErrorKind::ForeignKeyViolation,
+            "23502" => ErrorKind::NotNullViolation,
+            "23514" => ErrorKind::CheckViolation,
+            "40001" => ErrorKind::SerializationFailure,
+            "40P01" => ErrorKind::Deadlock,
+            "55P03" => ErrorKind::LockTimeout,
+
+            _ => ErrorKind::Other,
+        }
+    }
+
     fn as_ref_err(&self) -> &(dyn StdError + Send + Sync + 'static) {
         self
     }
@@ -94,6 +109,7 @@ fn test_error_downcasting() {
         e => panic!("expected Error::Database, got {:?}", e),
     };
 
+    assert_eq!(db_err.kind(), ErrorKind::Other);
     assert_eq!(db_err.downcast_ref::<PgError>().0.severity, Severity::Panic);
     assert_eq!(db_err.downcast::<PgError>().0.severity, Severity::Panic);
 }
@@ -8,7 +8,8 @@ use futures_util::{stream, StreamExt, TryStreamExt};
 use crate::arguments::Arguments;
 use crate::cursor::Cursor;
 use crate::describe::{Column, Describe};
-use crate::executor::{Execute, Executor, RefExecutor};
+use crate::executor::{Execute, Executor, QueryResult, RefExecutor, StatementResult};
+use crate::logging::QueryLogger;
 use crate::postgres::protocol::{
     self, CommandComplete, Message, ParameterDescription, ReadyForQuery, RowDescription,
     StatementId, TypeFormat, TypeId,
@@ -22,6 +23,7 @@ use crate::postgres::{
 };
 use crate::query_as::query_as;
 use crate::row::Row;
+use crate::statement::{check_connection, Statement as PreparedStatement};
 
 impl PgConnection {
     pub(crate) fn write_simple_query(&mut self, query: &str) {
@@ -33,7 +35,7 @@ impl PgConnection {
         query: &str,
         args: &PgArguments,
     ) -> crate::Result<StatementId> {
-        if let Some(&id) = self.cache_statement_id.get(query) {
+        if let Some(&mut id) = self.cache_statement_id.get_mut(query) {
             Ok(id)
         } else {
             let id = StatementId(self.next_statement_id);
@@ -69,10 +71,16 @@ impl PgConnection {
             self.is_ready = false;
 
             // wait for `ParseComplete`
-            match self.stream.receive().await? {
-                Message::ParseComplete => {}
-                message => {
-                    return Err(protocol_err!("run: unexpected message: {:?}", message).into());
+            loop {
+                match self.stream.receive().await? {
+                    Message::ParseComplete => break,
+
+                    // a statement evicted from the cache was closed
+                    Message::CloseComplete => {}
+
+                    message => {
+                        return Err(protocol_err!("run: unexpected message: {:?}", message).into());
+                    }
                 }
             }
 
@@ -83,13 +91,34 @@ impl PgConnection {
             let statement = self.expect_row_desc(pd).await?;
 
             // cache statement ID and statement description
-            self.cache_statement_id.insert(query.into(), id);
             self.cache_statement.insert(id, Arc::new(statement));
 
+            // close statements evicted from the cache; this is sent along with the next command
+            for evicted in self.cache_statement_id.insert(query, id) {
+                self.cache_statement.remove(&evicted);
+                self.stream.write(protocol::Close::Statement(evicted));
+            }
+
             Ok(id)
         }
     }
 
+    pub(crate) async fn clear_statement_cache(&mut self) -> crate::Result<()> {
+        self.wait_until_ready().await?;
+
+        for id in self.cache_statement_id.clear() {
+            self.cache_statement.remove(&id);
+            self.stream.write(protocol::Close::Statement(id));
+        }
+
+        self.write_sync();
+        self.stream.flush().await?;
+        self.is_ready = false;
+
+        // the server responds with `CloseComplete` for each statement and then `ReadyForQuery`
+        self.wait_until_ready().await
+    }
+
     async fn parse_parameter_description(
         &mut self,
         pd: ParameterDescription,
@@ -233,6 +262,7 @@ impl PgConnection {
             loop {
                 if let Message::ReadyForQuery = self.stream.receive().await? {
                     // we are now ready to go
+                    self.transaction_status = ReadyForQuery::read(self.stream.buffer())?.status();
                     self.is_ready = true;
                     break;
                 }
@@ -307,6 +337,16 @@ impl PgConnection {
         })
     }
 
+    pub(crate) async fn prepare_statement(
+        &mut self,
+        query: &str,
+    ) -> crate::Result<PreparedStatement<Postgres>> {
+        // [write_prepare] keeps the statement in the cache so executing it does not parse it again
+        let describe = self.do_describe(query).await?;
+
+        Ok(PreparedStatement::new(query, self.id, describe))
+    }
+
     pub(crate) async fn get_type_id_by_name(&mut self, name: &str) -> crate::Result<u32> {
         if let Some(oid) = self.cache_type_oid.get(name) {
             return Ok(*oid);
@@ -436,15 +476,17 @@ SELECT oid FROM pg_catalog.pg_type WHERE typname ILIKE $1
             .await
     }
 
-    // Poll messages from Postgres, counting the rows affected, until we finish the query
+    // Poll messages from Postgres, collecting the result of each statement, until we finish
+    // the query
     // This must be called directly after a call to [PgConnection::execute]
-    async fn affected_rows(&mut self) -> crate::Result<u64> {
-        let mut rows = 0;
+    async fn affected_rows(&mut self) -> crate::Result<QueryResult> {
+        let mut result = QueryResult::default();
 
         loop {
             match self.stream.receive().await? {
                 Message::ParseComplete
                 | Message::BindComplete
+                | Message::CloseComplete
                 | Message::NoData
                 | Message::EmptyQueryResponse
                 | Message::RowDescription => {}
@@ -455,13 +497,20 @@ SELECT oid FROM pg_catalog.pg_type WHERE typname ILIKE $1
                 }
 
                 Message::CommandComplete => {
-                    rows += CommandComplete::read(self.stream.buffer())?.affected_rows;
+                    let complete = CommandComplete::read(self.stream.buffer())?;
+
+                    result.push(StatementResult {
+                        rows_affected: complete.affected_rows,
+                        last_insert_id: None,
+                        command: Some(complete.command),
+                    });
                 }
 
                 Message::ReadyForQuery => {
                     // TODO: How should we handle an ERROR status form ReadyForQuery
-                    let _ready = ReadyForQuery::read(self.stream.buffer())?;
+                    let ready = ReadyForQuery::read(self.stream.buffer())?;
 
+                    self.transaction_status = ready.status();
                     self.is_ready = true;
                     break;
                 }
@@ -474,7 +523,7 @@ SELECT oid FROM pg_catalog.pg_type WHERE typname ILIKE $1
             }
         }
 
-        Ok(rows)
+        Ok(result)
     }
 }
 
@@ -484,17 +533,26 @@ impl Executor for super::PgConnection {
     fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
         &'c mut self,
         query: E,
-    ) -> BoxFuture<'e, crate::Result<u64>>
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, {
-            Box::pin(async move {
-                let (query, arguments) = query.into_parts();
+        Box::pin(async move {
+            check_connection(query.connection_id(), self.id)?;
 
-                self.run(query, arguments).await?;
-                self.affected_rows().await
-            })
+            let (query, arguments) = query.into_parts();
+            let mut logger = QueryLogger::new(query, &self.log_settings);
+
+            if let Some(arguments) = &arguments {
+                logger.arguments(|| arguments.log_values());
+            }
+
+            self.run(query, arguments).await?;
+
+            let result = self.affected_rows().await?;
+            logger.add_rows_affected(result.rows_affected());
+
+            Ok(result)
         })
     }
 
@@ -502,7 +560,7 @@ impl Executor for super::PgConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { PgCursor::from_connection(self, query) })
+        PgCursor::from_connection(self, query)
     }
 
     #[doc(hidden)]
@@ -524,6 +582,6 @@ impl<'c> RefExecutor<'c> for &'c mut super::PgConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { PgCursor::from_connection(self, query) })
+        PgCursor::from_connection(self, query)
     }
 }
@@ -7,9 +7,9 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::Stream;
 
 use crate::describe::Describe;
-use crate::executor::{Execute, Executor, RefExecutor};
+use crate::executor::{Execute, Executor, QueryResult, RefExecutor};
 use crate::pool::{Pool, PoolConnection};
-use crate::postgres::protocol::{Message, NotificationResponse};
+use crate::postgres::protocol::{Message, NotificationResponse, ReadyForQuery};
 use crate::postgres::{PgConnection, PgCursor, Postgres};
 
 /// A stream of asynchronous notifications from Postgres.
@@ -154,6 +154,9 @@ impl PgListener {
 
                 // Mark the connection as ready for another query
                 Ok(Message::ReadyForQuery) => {
+                    let ready = ReadyForQuery::read(self.connection().stream.buffer())?;
+
+                    self.connection().transaction_status = ready.status();
                     self.connection().is_ready = true;
                 }
 
@@ -194,7 +197,7 @@ impl Executor for PgListener {
     fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
         &'c mut self,
         query: E,
-    ) -> BoxFuture<'e, crate::Result<u64>>
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
     where
         E: Execute<'q, Self::Database>,
     {
@@ -7,6 +7,7 @@ pub use cursor::PgCursor;
 pub use database::Postgres;
 pub use error::PgError;
 pub use listen::{PgListener, PgNotification};
+pub use options::{PgConnectOptions, PgSslMode, PgTargetSessionAttrs};
 pub use row::PgRow;
 pub use type_info::PgTypeInfo;
 pub use value::{PgData, PgValue};
@@ -14,11 +15,13 @@ pub use value::{PgData, PgValue};
 mod arguments;
 mod buffer;
 mod connection;
-mod cursor;
+pub(crate) mod cursor;
 mod database;
 mod error;
 mod executor;
 mod listen;
+mod options;
+mod pgpass;
 mod protocol;
 mod row;
 mod sasl;
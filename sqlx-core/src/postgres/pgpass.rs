@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+use std::env;
+use std::path::PathBuf;
+
+use crate::postgres::PgConnectOptions;
+use crate::runtime::fs;
+
+// Looks up the password of a connection in the password file, as `libpq` does
+// https://www.postgresql.org/docs/12/libpq-pgpass.html
+pub(super) async fn load_password(options: &PgConnectOptions) -> Option<String> {
+    let path = options.passfile.clone().or_else(default_passfile)?;
+
+    // The file is ignored if it can be read by anyone but the user
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(&path).await.ok()?.permissions().mode();
+
+        if mode & 0o077 != 0 {
+            log::warn!(
+                "ignoring the password file {:?}, as its permissions should be u=rw (0600) or less",
+                path
+            );
+
+            return None;
+        }
+    }
+
+    let contents = fs::read_to_string(&path).await.ok()?;
+
+    // A connection over the default unix domain socket matches the host `localhost`
+    let host = match &options.socket {
+        Some(socket) if Some(socket) != super::options::default_socket().as_ref() => {
+            socket.to_string_lossy()
+        }
+
+        Some(_) => Cow::Borrowed("localhost"),
+        None => Cow::Borrowed(&*options.host),
+    };
+
+    find_password(
+        &contents,
+        &host,
+        options.port,
+        options.database_or_username(),
+        &options.username,
+    )
+}
+
+fn default_passfile() -> Option<PathBuf> {
+    if cfg!(windows) {
+        env::var_os("APPDATA")
+            .map(|app_data| PathBuf::from(app_data).join("postgresql\\pgpass.conf"))
+    } else {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".pgpass"))
+    }
+}
+
+// Each line is `hostname:port:database:username:password`, where any of the first four fields
+// may be `*`, and `:` or `\` in a field are escaped with `\`
+fn find_password(
+    contents: &str,
+    host: &str,
+    port: u16,
+    database: &str,
+    username: &str,
+) -> Option<String> {
+    let port = port.to_string();
+
+    contents
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| {
+            let fields = split_fields(line);
+
+            if fields.len() != 5 {
+                return None;
+            }
+
+            let matches = [host, &port, database, username]
+                .iter()
+                .zip(&fields)
+                .all(|(value, field)| field == "*" || field == value);
+
+            if matches {
+                Some(fields[4].clone())
+            } else {
+                None
+            }
+        })
+}
+
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => fields.last_mut().unwrap().extend(chars.next()),
+            ':' if fields.len() < 5 => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_password;
+
+    const PGPASS: &str = r"# comment
+db.example.com:5432:app:alice:first
+*:5432:*:bob:pass\:word\\
+localhost:*:*:*:fallback
+";
+
+    #[test]
+    fn it_finds_the_first_matching_line() {
+        assert_eq!(
+            find_password(PGPASS, "db.example.com", 5432, "app", "alice").as_deref(),
+            Some("first")
+        );
+        assert_eq!(
+            find_password(PGPASS, "db.example.com", 5432, "other", "bob").as_deref(),
+            Some("pass:word\\")
+        );
+        assert_eq!(
+            find_password(PGPASS, "localhost", 5433, "app", "alice").as_deref(),
+            Some("fallback")
+        );
+    }
+
+    #[test]
+    fn it_finds_no_password_without_a_match() {
+        assert_eq!(
+            find_password(PGPASS, "db.example.com", 5433, "app", "alice"),
+            None
+        );
+        assert_eq!(find_password("a:b:c", "a", 5432, "c", "d"), None);
+    }
+}
@@ -4,6 +4,9 @@ use crate::postgres::database::Postgres;
 #[derive(Debug)]
 pub(crate) struct CommandComplete {
     pub(crate) affected_rows: u64,
+
+    // The command tag without its counts, e.g. "INSERT" or "CREATE TABLE"
+    pub(crate) command: Box<str>,
 }
 
 impl CommandComplete {
@@ -12,15 +15,20 @@ impl CommandComplete {
         // If it can't be parsed, the tag is probably "CREATE TABLE" or something
         // and we should return 0 rows
 
-        let rows = buf
-            .get_str_nul()?
+        let tag = buf.get_str_nul()?;
+
+        let rows = tag
             .rsplit(' ')
             .next()
             .and_then(|s| s.parse().ok())
             .unwrap_or(0);
 
+        // The counts follow the name of the command, e.g. "INSERT 0 1" (the 0 is an OID)
+        let command = tag.trim_end_matches(|c: char| c == ' ' || c.is_ascii_digit());
+
         Ok(Self {
             affected_rows: rows,
+            command: command.into(),
         })
     }
 }
@@ -39,6 +47,7 @@ mod tests {
         let message = CommandComplete::read(COMMAND_COMPLETE_INSERT).unwrap();
 
         assert_eq!(message.affected_rows, 1);
+        assert_eq!(&*message.command, "INSERT");
     }
 
     #[test]
@@ -60,5 +69,6 @@ mod tests {
         let message = CommandComplete::read(COMMAND_COMPLETE_CREATE_TABLE).unwrap();
 
         assert_eq!(message.affected_rows, 0);
+        assert_eq!(&*message.command, "CREATE TABLE");
     }
 }
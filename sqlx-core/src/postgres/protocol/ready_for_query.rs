@@ -1,6 +1,6 @@
 use crate::postgres::database::Postgres;
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u8)]
 pub enum TransactionStatus {
     /// Not in a transaction block.
@@ -20,6 +20,10 @@ pub struct ReadyForQuery {
 }
 
 impl ReadyForQuery {
+    pub(crate) fn status(&self) -> TransactionStatus {
+        self.status
+    }
+
     pub(crate) fn read(buf: &[u8]) -> crate::Result<Self> {
         Ok(Self {
             status: match buf[0] {
@@ -1,14 +1,15 @@
 use std::convert::TryInto;
+#[cfg(not(unix))]
+use std::io;
 use std::net::Shutdown;
 
 use byteorder::NetworkEndian;
 use futures_channel::mpsc::UnboundedSender;
 
 use crate::io::{Buf, BufStream, MaybeTlsStream};
-use crate::postgres::protocol::{Message, NotificationResponse, Response, Write};
-use crate::postgres::PgError;
+use crate::postgres::protocol::{Message, NotificationResponse, Response, Severity, Write};
+use crate::postgres::{PgConnectOptions, PgError};
 
-use crate::url::Url;
 use futures_util::SinkExt;
 
 pub struct PgStream {
@@ -19,36 +20,43 @@ pub struct PgStream {
     // Is referenced by our buffered stream
     // Is initialized to ReadyForQuery/0 at the start
     pub(super) message: (Message, u32),
+
+    // Set once the connection can no longer be used, because it was lost or closed by the
+    // server, or because the server stopped accepting writes while they are required
+    pub(super) is_broken: bool,
+
+    // Whether the session was required to accept writes by `target_session_attrs`
+    pub(super) requires_writes: bool,
 }
 
 impl PgStream {
-    pub(super) async fn new(url: &Url) -> crate::Result<Self> {
-        let host = url.host();
-        let port = url.port(5432);
-        #[cfg(unix)]
-        let stream = {
-            let host = host
-                .map(|host| {
-                    percent_encoding::percent_decode_str(host)
-                        .decode_utf8()
-                        .expect("percent-encoded hostname contained non-UTF-8 bytes")
-                })
-                .or_else(|| url.param("host"))
-                .unwrap_or("/var/run/postgresql".into());
-            if host.starts_with("/") {
-                let path = format!("{}/.s.PGSQL.{}", host, port);
+    pub(super) async fn new(options: &PgConnectOptions) -> crate::Result<Self> {
+        let stream = match &options.socket {
+            #[cfg(unix)]
+            Some(socket) => {
+                let path = socket.join(format!(".s.PGSQL.{}", options.port));
+
                 MaybeTlsStream::connect_uds(&path).await?
-            } else {
-                MaybeTlsStream::connect(&host, port).await?
             }
+
+            #[cfg(not(unix))]
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "unix domain sockets are not supported on this platform",
+                )
+                .into());
+            }
+
+            None => MaybeTlsStream::connect(&options.host, options.port).await?,
         };
-        #[cfg(not(unix))]
-        let stream = MaybeTlsStream::connect(host.unwrap_or("localhost"), port).await?;
 
         Ok(Self {
             notifications: None,
             stream: BufStream::new(stream),
             message: (Message::ReadyForQuery, 0),
+            is_broken: false,
+            requires_writes: false,
         })
     }
 
@@ -66,7 +74,13 @@ impl PgStream {
 
     #[inline]
     pub(super) async fn flush(&mut self) -> crate::Result<()> {
-        Ok(self.stream.flush().await?)
+        let result = self.stream.flush().await;
+
+        if result.is_err() {
+            self.is_broken = true;
+        }
+
+        Ok(result?)
     }
 
     pub(super) async fn read(&mut self) -> crate::Result<Message> {
@@ -99,12 +113,30 @@ impl PgStream {
 
     pub(super) async fn receive(&mut self) -> crate::Result<Message> {
         loop {
-            let type_ = self.read().await?;
+            let type_ = match self.read().await {
+                Ok(type_) => type_,
+
+                // The stream is lost, or out of step with the server
+                Err(error) => {
+                    self.is_broken = true;
+                    return Err(error);
+                }
+            };
 
             match type_ {
                 Message::ErrorResponse | Message::NoticeResponse => {
                     let response = Response::read(self.stream.buffer())?;
 
+                    // The server closes the connection after a fatal error, e.g. when it is
+                    // shut down; a read-only transaction error means that a primary has
+                    // become a standby
+                    if response.severity == Severity::Fatal
+                        || response.severity == Severity::Panic
+                        || (self.requires_writes && &*response.code == "25006")
+                    {
+                        self.is_broken = true;
+                    }
+
                     if response.severity.is_error() {
                         // This is an error, bubble up as one immediately
                         return Err(crate::Error::Database(Box::new(PgError(response))));
@@ -1,39 +1,41 @@
 use crate::postgres::stream::PgStream;
-use crate::url::Url;
+use crate::postgres::{PgConnectOptions, PgSslMode};
 
 #[cfg_attr(not(feature = "tls"), allow(unused_variables))]
-pub(crate) async fn request_if_needed(stream: &mut PgStream, url: &Url) -> crate::Result<()> {
+pub(crate) async fn request_if_needed(
+    stream: &mut PgStream,
+    options: &PgConnectOptions,
+) -> crate::Result<()> {
     // https://www.postgresql.org/docs/12/libpq-ssl.html#LIBPQ-SSL-SSLMODE-STATEMENTS
-    match url.param("sslmode").as_deref() {
-        Some("disable") | Some("allow") => {
+    match options.ssl_mode {
+        PgSslMode::Disable | PgSslMode::Allow => {
             // Do nothing
         }
 
         #[cfg(feature = "tls")]
-        Some("prefer") | None => {
+        PgSslMode::Prefer => {
             // We default to [prefer] if TLS is compiled in
-            if !try_upgrade(stream, url, true, true).await? {
+            if !try_upgrade(stream, options, true, true).await? {
                 // TLS upgrade failed; fall back to a normal connection
             }
         }
 
         #[cfg(not(feature = "tls"))]
-        None => {
-            // The user neither explicitly enabled TLS in the connection string
-            // nor did they turn the `tls` feature on
+        PgSslMode::Prefer => {
+            // The user did not turn the `tls` feature on
 
             // Do nothing
         }
 
         #[cfg(feature = "tls")]
-        Some(mode @ "require") | Some(mode @ "verify-ca") | Some(mode @ "verify-full") => {
+        mode @ PgSslMode::Require | mode @ PgSslMode::VerifyCa | mode @ PgSslMode::VerifyFull => {
             if !try_upgrade(
                 stream,
-                url,
+                options,
                 // false for both verify-ca and verify-full
-                mode == "require",
+                mode == PgSslMode::Require,
                 // false for only verify-full
-                mode != "verify-full",
+                mode != PgSslMode::VerifyFull,
             )
             .await?
             {
@@ -42,20 +44,13 @@ pub(crate) async fn request_if_needed(stream: &mut PgStream, url: &Url) -> crate
         }
 
         #[cfg(not(feature = "tls"))]
-        Some(mode @ "prefer")
-        | Some(mode @ "require")
-        | Some(mode @ "verify-ca")
-        | Some(mode @ "verify-full") => {
+        mode @ PgSslMode::Require | mode @ PgSslMode::VerifyCa | mode @ PgSslMode::VerifyFull => {
             return Err(tls_err!(
                 "sslmode {:?} unsupported; SQLx was compiled without `tls` feature",
                 mode
             )
             .into());
         }
-
-        Some(mode) => {
-            return Err(tls_err!("unknown `sslmode` value: {:?}", mode).into());
-        }
     }
 
     Ok(())
@@ -64,12 +59,11 @@ pub(crate) async fn request_if_needed(stream: &mut PgStream, url: &Url) -> crate
 #[cfg(feature = "tls")]
 async fn try_upgrade(
     stream: &mut PgStream,
-    url: &Url,
+    options: &PgConnectOptions,
     accept_invalid_certs: bool,
     accept_invalid_host_names: bool,
 ) -> crate::Result<bool> {
     use async_native_tls::TlsConnector;
-    use std::borrow::Cow;
 
     stream.write(crate::postgres::protocol::SslRequest);
     stream.flush().await?;
@@ -101,33 +95,27 @@ async fn try_upgrade(
     if !accept_invalid_certs {
         // Try to read in the root certificate for postgres using several
         // standard methods (used by psql and libpq)
-        if let Some(cert) = read_root_certificate(&url).await? {
+        if let Some(cert) = read_root_certificate(options).await? {
             connector = connector.add_root_certificate(cert);
         }
     }
 
-    let host = url
-        .host()
-        .map(Cow::Borrowed)
-        .or_else(|| url.param("host"))
-        .unwrap_or("localhost".into());
-    stream.stream.upgrade(&host, connector).await?;
+    stream.stream.upgrade(&options.host, connector).await?;
 
     Ok(true)
 }
 
 #[cfg(feature = "tls")]
-async fn read_root_certificate(url: &Url) -> crate::Result<Option<async_native_tls::Certificate>> {
+async fn read_root_certificate(
+    options: &PgConnectOptions,
+) -> crate::Result<Option<async_native_tls::Certificate>> {
     use crate::runtime::fs;
     use std::env;
 
     let mut data = None;
 
-    if let Some(path) = url
-        .param("sslrootcert")
-        .or_else(|| env::var("PGSSLROOTCERT").ok().map(Into::into))
-    {
-        data = Some(fs::read(&*path).await?);
+    if let Some(path) = &options.ssl_root_cert {
+        data = Some(fs::read(path).await?);
     } else if cfg!(windows) {
         if let Ok(app_data) = env::var("APPDATA") {
             let path = format!("{}\\postgresql\\root.crt", app_data);
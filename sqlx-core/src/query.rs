@@ -9,7 +9,7 @@ use crate::arguments::Arguments;
 use crate::cursor::{Cursor, HasCursor};
 use crate::database::Database;
 use crate::encode::Encode;
-use crate::executor::{Execute, Executor, RefExecutor};
+use crate::executor::{Execute, Executor, QueryResult, RefExecutor};
 use crate::row::HasRow;
 use crate::types::Type;
 
@@ -21,7 +21,8 @@ where
 {
     pub(crate) query: &'q str,
     pub(crate) arguments: DB::Arguments,
-    database: PhantomData<DB>,
+    pub(crate) connection_id: Option<u64>,
+    pub(crate) database: PhantomData<DB>,
 }
 
 /// SQL query that will map its results to owned Rust types.
@@ -56,6 +57,11 @@ where
     fn query_string(&self) -> &'q str {
         self.query
     }
+
+    #[doc(hidden)]
+    fn connection_id(&self) -> Option<u64> {
+        self.connection_id
+    }
 }
 
 impl<'q, DB> Query<'q, DB>
@@ -84,6 +90,7 @@ where
         Query {
             query: self.query,
             arguments,
+            connection_id: self.connection_id,
             database: PhantomData,
         }
     }
@@ -126,7 +133,7 @@ where
     DB: Database,
     Self: Execute<'q, DB>,
 {
-    pub async fn execute<E>(self, mut executor: E) -> crate::Result<u64>
+    pub async fn execute<E>(self, mut executor: E) -> crate::Result<QueryResult>
     where
         E: Executor<Database = DB>,
     {
@@ -251,6 +258,7 @@ where
     Query {
         database: PhantomData,
         arguments: Default::default(),
+        connection_id: None,
         query: sql,
     }
 }
@@ -14,10 +14,11 @@ pub struct QueryAs<'q, DB, O>
 where
     DB: Database,
 {
-    query: &'q str,
-    arguments: <DB as Database>::Arguments,
-    database: PhantomData<DB>,
-    output: PhantomData<O>,
+    pub(crate) query: &'q str,
+    pub(crate) arguments: <DB as Database>::Arguments,
+    pub(crate) connection_id: Option<u64>,
+    pub(crate) database: PhantomData<DB>,
+    pub(crate) output: PhantomData<O>,
 }
 
 impl<'q, DB, O> QueryAs<'q, DB, O>
@@ -50,6 +51,12 @@ where
     fn query_string(&self) -> &'q str {
         self.query
     }
+
+    #[inline]
+    #[doc(hidden)]
+    fn connection_id(&self) -> Option<u64> {
+        self.connection_id
+    }
 }
 
 /// Construct a raw SQL query that is mapped to a concrete type
@@ -63,6 +70,7 @@ where
     QueryAs {
         query: sql,
         arguments: Default::default(),
+        connection_id: None,
         database: PhantomData,
         output: PhantomData,
     }
@@ -1,5 +1,7 @@
 //! Contains the `ColumnIndex`, `Row`, and `FromRow` traits.
 
+use std::fmt::Debug;
+
 use crate::database::Database;
 use crate::decode::Decode;
 use crate::types::{Type, TypeInfo};
@@ -18,7 +20,7 @@ use crate::value::{HasRawValue, RawValue};
 /// [`try_get`]: trait.Row.html#method.try_get
 pub trait ColumnIndex<'c, R>
 where
-    Self: private_column_index::Sealed,
+    Self: private_column_index::Sealed + Debug,
     R: Row<'c> + ?Sized,
 {
     /// Returns a valid positional index into the row, [`ColumnIndexOutOfBounds`], or,
@@ -31,7 +33,7 @@ where
 
 impl<'c, R, I> ColumnIndex<'c, R> for &'_ I
 where
-    R: Row<'c>,
+    R: Row<'c> + ?Sized,
     I: ColumnIndex<'c, R> + ?Sized,
 {
     #[inline]
@@ -134,9 +136,9 @@ where
     /// # Errors
     ///  * [`ColumnNotFound`] if the column by the given name was not found.
     ///  * [`ColumnIndexOutOfBounds`] if the `usize` index was greater than the number of columns in the row.
-    ///  * [`Decode`] if the value could not be decoded into the requested type.
+    ///  * [`ColumnDecode`] if the value could not be decoded into the requested type.
     ///
-    /// [`Decode`]: crate::Error::Decode
+    /// [`ColumnDecode`]: crate::Error::ColumnDecode
     /// [`ColumnNotFound`]: crate::Error::ColumnNotFound
     /// [`ColumnIndexOutOfBounds`]: crate::Error::ColumnIndexOutOfBounds
     fn try_get<T, I>(&self, index: I) -> crate::Result<T>
@@ -145,7 +147,7 @@ where
         I: ColumnIndex<'c, Self>,
         T: Decode<'c, Self::Database>,
     {
-        let value = self.try_get_raw(index)?;
+        let value = self.try_get_raw(&index)?;
 
         if let Some(expected_ty) = value.type_info() {
             // NOTE: If there is no type, the value is NULL. This is fine. If the user tries
@@ -153,13 +155,14 @@ where
             //       UnexpectedNullError.
 
             if !expected_ty.compatible(&T::type_info()) {
-                return Err(crate::Error::mismatched_types::<Self::Database, T>(
-                    expected_ty,
-                ));
+                return Err(
+                    crate::Error::mismatched_types::<Self::Database, T>(expected_ty)
+                        .in_column(index),
+                );
             }
         }
 
-        T::decode(value)
+        T::decode(value).map_err(|error| error.in_column(index))
     }
 
     /// Index into the database row and decode a single value.
@@ -175,7 +178,9 @@ where
         I: ColumnIndex<'c, Self>,
         T: Decode<'c, Self::Database>,
     {
-        self.try_get_raw(index).and_then(T::decode)
+        let value = self.try_get_raw(&index)?;
+
+        T::decode(value).map_err(|error| error.in_column(index))
     }
 
     #[doc(hidden)]
@@ -224,6 +229,52 @@ pub trait HasRow<'c> {
 /// }
 /// ```
 ///
+/// The derive accepts the following attributes:
+///
+///  * `#[sqlx(rename_all = "..")]` on the struct renames every column: one of `lowercase`,
+///    `UPPERCASE`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case`, `camelCase` or
+///    `PascalCase`.
+///  * `#[sqlx(rename = "..")]` on a field sets the name of its column.
+///  * `#[sqlx(default)]` on a field uses [`Default::default`] if the column is not in the row.
+///  * `#[sqlx(skip)]` on a field does not read it from the row and always uses
+///    [`Default::default`].
+///  * `#[sqlx(flatten)]` on a field decodes it with its own `FromRow` implementation from the
+///    same row, e.g. to share a set of columns between several records.
+///  * `#[sqlx(try_from = "T")]` on a field decodes the column as `T` and converts it to the type
+///    of the field with [`TryFrom`](std::convert::TryFrom).
+///  * `#[sqlx(with = "path")]` on a field decodes the column as the type taken by the function
+///    at `path`, which returns a `Result` of the field type. Together with `try_from`, the
+///    column is decoded as the `try_from` type instead.
+///
+/// As the type a `with` function takes cannot be named in the bounds of an implementation for
+/// any row, a `with` without `try_from` implements `FromRow` for the row type of each enabled
+/// database (e.g. `SqliteRow`) instead, and not for `AnyRow`.
+///
+/// The error of a conversion must convert into `Box<dyn Error + Send + Sync>`.
+///
+/// A failed conversion is returned as [`Error::ColumnDecode`](crate::Error::ColumnDecode).
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct Audit {
+///     created_at: NaiveDateTime,
+///     updated_at: NaiveDateTime,
+/// }
+///
+/// #[derive(sqlx::FromRow)]
+/// #[sqlx(rename_all = "camelCase")]
+/// struct Post {
+///     post_id: i32,
+///     #[sqlx(flatten)]
+///     audit: Audit,
+///     #[sqlx(skip)]
+///     comments: Vec<Comment>,
+/// }
+/// ```
+///
+/// Tuple structs are decoded by position; the first field is read from the first column and so
+/// on (fields marked `#[sqlx(skip)]` do not take a column).
+///
 /// [`query_as`]: crate::query_as
 /// [`Row::try_get`]: crate::row::Row::try_get
 pub trait FromRow<'c, R>
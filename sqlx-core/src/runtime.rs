@@ -17,7 +17,11 @@ pub(crate) use async_std::{
     task::spawn,
 };
 
-#[cfg(all(feature = "runtime-async-std", feature = "postgres", unix))]
+#[cfg(all(
+    feature = "runtime-async-std",
+    any(feature = "postgres", feature = "mysql"),
+    unix
+))]
 pub(crate) use async_std::os::unix::net::UnixStream;
 
 #[cfg(feature = "runtime-tokio")]
@@ -30,5 +34,9 @@ pub(crate) use tokio::{
     time::timeout,
 };
 
-#[cfg(all(feature = "runtime-tokio", feature = "postgres", unix))]
+#[cfg(all(
+    feature = "runtime-tokio",
+    any(feature = "postgres", feature = "mysql"),
+    unix
+))]
 pub(crate) use tokio::net::UnixStream;
@@ -50,6 +50,21 @@ impl SqliteArguments {
         self.index += 1;
         Some(value)
     }
+
+    // Renders each bound value for the query log
+    pub(crate) fn log_values(&self) -> Vec<String> {
+        self.values
+            .iter()
+            .map(|value| match value {
+                SqliteArgumentValue::Null => "NULL".to_owned(),
+                SqliteArgumentValue::Text(text) => format!("{:?}", text),
+                SqliteArgumentValue::Blob(blob) => format!("x'{}'", hex::encode(blob)),
+                SqliteArgumentValue::Double(value) => value.to_string(),
+                SqliteArgumentValue::Int(value) => value.to_string(),
+                SqliteArgumentValue::Int64(value) => value.to_string(),
+            })
+            .collect()
+    }
 }
 
 impl Arguments for SqliteArguments {
@@ -1,23 +1,25 @@
 use core::ptr::{null, null_mut, NonNull};
 
-use std::collections::HashMap;
-use std::convert::TryInto;
 use std::ffi::CString;
 
 use futures_core::future::BoxFuture;
 use futures_util::future;
 use libsqlite3_sys::{
-    sqlite3, sqlite3_close, sqlite3_extended_result_codes, sqlite3_open_v2, SQLITE_OK,
-    SQLITE_OPEN_CREATE, SQLITE_OPEN_NOMUTEX, SQLITE_OPEN_READWRITE, SQLITE_OPEN_SHAREDCACHE,
+    sqlite3, sqlite3_close, sqlite3_extended_result_codes, sqlite3_get_autocommit, sqlite3_open_v2,
+    SQLITE_OK, SQLITE_OPEN_CREATE, SQLITE_OPEN_NOMUTEX, SQLITE_OPEN_READWRITE,
+    SQLITE_OPEN_SHAREDCACHE,
 };
 
+use crate::cache::StatementCache;
 use crate::connection::{Connect, Connection};
 use crate::executor::Executor;
+use crate::logging::LogSettings;
 use crate::sqlite::statement::Statement;
 use crate::sqlite::worker::Worker;
 
-use crate::sqlite::SqliteError;
-use crate::url::Url;
+use crate::sqlite::{Sqlite, SqliteConnectOptions, SqliteError};
+use crate::statement::{next_connection_id, Statement as PreparedStatement};
+use crate::transaction::{IsolationLevel, TransactionOptions};
 
 /// Thin wrapper around [sqlite3] to impl `Send`.
 #[derive(Clone, Copy)]
@@ -25,13 +27,16 @@ pub(super) struct SqliteConnectionHandle(pub(super) NonNull<sqlite3>);
 
 /// A connection to a [Sqlite](struct.Sqlite.html) database.
 pub struct SqliteConnection {
+    pub(crate) id: u64,
     pub(super) handle: SqliteConnectionHandle,
     pub(super) worker: Worker,
     // Storage of the most recently prepared, non-persistent statement
     pub(super) statement: Option<Statement>,
-    // Storage of persistent statements
-    pub(super) statements: Vec<Statement>,
-    pub(super) statement_by_query: HashMap<String, usize>,
+    // Storage of persistent statements; a slot is emptied when its statement is evicted
+    pub(super) statements: Vec<Option<Statement>>,
+    pub(super) vacant_statements: Vec<usize>,
+    pub(super) statement_by_query: StatementCache<usize>,
+    pub(super) log_settings: LogSettings,
 }
 
 // A SQLite3 handle is safe to send between threads, provided not more than
@@ -45,12 +50,24 @@ pub struct SqliteConnection {
 
 unsafe impl Send for SqliteConnectionHandle {}
 
-async fn establish(url: Result<Url, url::ParseError>) -> crate::Result<SqliteConnection> {
+async fn establish(options: &SqliteConnectOptions) -> crate::Result<SqliteConnection> {
     let mut worker = Worker::new();
 
-    // By default, we connect to an in-memory database.
-    // TODO: Handle the error when there are internal NULs in the database URL
-    let filename = CString::new(url?.path_decoded().to_string()).unwrap();
+    let filename = options
+        .filename
+        .to_str()
+        .and_then(|filename| CString::new(filename).ok())
+        .ok_or_else(|| {
+            crate::Error::Configuration(
+                format!("invalid database filename: {:?}", options.filename).into(),
+            )
+        })?;
+
+    let create = if options.create_if_missing {
+        SQLITE_OPEN_CREATE
+    } else {
+        0
+    };
 
     let handle = worker
         .run(move || -> crate::Result<SqliteConnectionHandle> {
@@ -58,10 +75,8 @@ async fn establish(url: Result<Url, url::ParseError>) -> crate::Result<SqliteCon
 
             // [SQLITE_OPEN_NOMUTEX] will instruct [sqlite3_open_v2] to return an error if it
             // cannot satisfy our wish for a thread-safe, lock-free connection object
-            let flags = SQLITE_OPEN_READWRITE
-                | SQLITE_OPEN_CREATE
-                | SQLITE_OPEN_NOMUTEX
-                | SQLITE_OPEN_SHAREDCACHE;
+            let flags =
+                SQLITE_OPEN_READWRITE | create | SQLITE_OPEN_NOMUTEX | SQLITE_OPEN_SHAREDCACHE;
 
             // <https://www.sqlite.org/c3ref/open.html>
             let status = unsafe { sqlite3_open_v2(filename.as_ptr(), &mut handle, flags, null()) };
@@ -92,11 +107,14 @@ async fn establish(url: Result<Url, url::ParseError>) -> crate::Result<SqliteCon
         .await?;
 
     Ok(SqliteConnection {
+        id: next_connection_id(),
         worker,
         handle,
         statement: None,
         statements: Vec::with_capacity(10),
-        statement_by_query: HashMap::with_capacity(10),
+        vacant_statements: Vec::new(),
+        statement_by_query: StatementCache::new(options.statement_cache_capacity),
+        log_settings: options.log_settings.clone(),
     })
 }
 
@@ -108,15 +126,11 @@ impl SqliteConnection {
 }
 
 impl Connect for SqliteConnection {
-    fn connect<T>(url: T) -> BoxFuture<'static, crate::Result<SqliteConnection>>
-    where
-        T: TryInto<Url, Error = url::ParseError>,
-        Self: Sized,
-    {
-        let url = url.try_into();
+    type Options = SqliteConnectOptions;
 
+    fn connect_with(options: &SqliteConnectOptions) -> BoxFuture<'_, crate::Result<Self>> {
         Box::pin(async move {
-            let mut conn = establish(url).await?;
+            let mut conn = establish(options).await?;
 
             // https://www.sqlite.org/wal.html
 
@@ -144,6 +158,55 @@ impl Connection for SqliteConnection {
         // For SQLite connections, PING does effectively nothing
         Box::pin(future::ok(()))
     }
+
+    fn cached_statements_size(&self) -> usize {
+        self.statement_by_query.len()
+    }
+
+    fn set_statement_cache_capacity(&mut self, capacity: usize) {
+        self.statement_by_query.set_capacity(capacity);
+    }
+
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>> {
+        // Dropping the statements finalizes them
+        self.clear_statement_cache();
+
+        Box::pin(future::ok(()))
+    }
+
+    fn log_settings(&self) -> &LogSettings {
+        &self.log_settings
+    }
+
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        self.log_settings = settings;
+    }
+
+    fn prepare<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, crate::Result<PreparedStatement<Sqlite>>> {
+        Box::pin(future::ready(self.prepare_statement(sql)))
+    }
+
+    fn in_transaction(&self) -> bool {
+        // https://www.sqlite.org/c3ref/get_autocommit.html
+        unsafe { sqlite3_get_autocommit(self.handle.0.as_ptr()) == 0 }
+    }
+
+    #[doc(hidden)]
+    fn begin_statements(&self, options: &TransactionOptions) -> Vec<String> {
+        // SQLite transactions are always serializable; the isolation level only decides
+        // when the transaction takes its locks
+        let statement = match options.isolation {
+            _ if options.read_only => "BEGIN",
+            None => "BEGIN",
+            Some(IsolationLevel::Serializable) => "BEGIN EXCLUSIVE",
+            Some(_) => "BEGIN IMMEDIATE",
+        };
+
+        vec![statement.to_owned()]
+    }
 }
 
 impl Drop for SqliteConnection {
@@ -3,15 +3,51 @@ use futures_core::future::BoxFuture;
 use crate::connection::ConnectionSource;
 use crate::cursor::Cursor;
 use crate::executor::Execute;
+use crate::logging::QueryLogger;
 use crate::pool::Pool;
 use crate::sqlite::statement::Step;
 use crate::sqlite::{Sqlite, SqliteArguments, SqliteConnection, SqliteRow};
+use crate::statement::check_connection;
 
 pub struct SqliteCursor<'c, 'q> {
     pub(super) source: ConnectionSource<'c, SqliteConnection>,
+    state: SqliteCursorState<'q>,
+}
+
+// The progress of a cursor through the result of its query, kept apart from the connection so
+// that the `Any` driver can read it from a connection it holds itself
+pub(crate) struct SqliteCursorState<'q> {
     query: &'q str,
     arguments: Option<SqliteArguments>,
-    pub(super) statement: Option<Option<usize>>,
+    connection_id: Option<u64>,
+    logger: Option<QueryLogger<'q>>,
+    statement: Option<Option<usize>>,
+}
+
+impl<'q> SqliteCursorState<'q> {
+    pub(crate) fn new(
+        query: &'q str,
+        arguments: Option<SqliteArguments>,
+        connection_id: Option<u64>,
+    ) -> Self {
+        Self {
+            query,
+            arguments,
+            connection_id,
+            logger: None,
+            statement: None,
+        }
+    }
+
+    fn from_query<E>(query: E) -> Self
+    where
+        E: Execute<'q, Sqlite>,
+    {
+        let connection_id = query.connection_id();
+        let (query, arguments) = query.into_parts();
+
+        Self::new(query, arguments, connection_id)
+    }
 }
 
 impl crate::cursor::private::Sealed for SqliteCursor<'_, '_> {}
@@ -25,13 +61,9 @@ impl<'c, 'q> Cursor<'c, 'q> for SqliteCursor<'c, 'q> {
         Self: Sized,
         E: Execute<'q, Sqlite>,
     {
-        let (query, arguments) = query.into_parts();
-
         Self {
             source: ConnectionSource::Pool(pool.clone()),
-            statement: None,
-            query,
-            arguments,
+            state: SqliteCursorState::from_query(query),
         }
     }
 
@@ -41,13 +73,9 @@ impl<'c, 'q> Cursor<'c, 'q> for SqliteCursor<'c, 'q> {
         Self: Sized,
         E: Execute<'q, Sqlite>,
     {
-        let (query, arguments) = query.into_parts();
-
         Self {
             source: ConnectionSource::ConnectionRef(conn),
-            statement: None,
-            query,
-            arguments,
+            state: SqliteCursorState::from_query(query),
         }
     }
 
@@ -61,9 +89,28 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
 ) -> crate::Result<Option<SqliteRow<'a>>> {
     let conn = cursor.source.resolve().await?;
 
+    next_row(conn, &mut cursor.state).await
+}
+
+pub(crate) async fn next_row<'a, 'q: 'a>(
+    conn: &'a mut SqliteConnection,
+    cursor: &'a mut SqliteCursorState<'q>,
+) -> crate::Result<Option<SqliteRow<'a>>> {
     loop {
         if cursor.statement.is_none() {
-            let key = conn.prepare(&mut cursor.query, cursor.arguments.is_some())?;
+            check_connection(cursor.connection_id, conn.id)?;
+
+            if cursor.logger.is_none() {
+                let mut logger = QueryLogger::new(cursor.query, &conn.log_settings);
+
+                if let Some(arguments) = &cursor.arguments {
+                    logger.arguments(|| arguments.log_values());
+                }
+
+                cursor.logger = Some(logger);
+            }
+
+            let key = conn.get_or_prepare(&mut cursor.query, cursor.arguments.is_some())?;
 
             if let Some(arguments) = &mut cursor.arguments {
                 conn.statement_mut(key).bind(arguments)?;
@@ -79,6 +126,10 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
 
         match step {
             Step::Row => {
+                if let Some(logger) = &mut cursor.logger {
+                    logger.increment_rows_returned();
+                }
+
                 return Ok(Some(SqliteRow {
                     values: statement.data_count(),
                     statement: key,
@@ -87,6 +138,9 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
             }
 
             Step::Done if cursor.query.is_empty() => {
+                // The query is logged once its result has been consumed
+                cursor.logger = None;
+
                 return Ok(None);
             }
 
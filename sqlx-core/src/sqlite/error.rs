@@ -1,4 +1,4 @@
-use crate::error::DatabaseError;
+use crate::error::{DatabaseError, ErrorKind};
 
 use bitflags::_core::str::from_utf8_unchecked;
 use libsqlite3_sys::{sqlite3, sqlite3_errmsg, sqlite3_extended_errcode};
@@ -49,6 +49,31 @@ impl DatabaseError for SqliteError {
         Some(&self.code)
     }
 
+    fn kind(&self) -> ErrorKind {
+        // https://www.sqlite.org/rescode.html
+        match self.code.parse::<c_int>() {
+            // SQLITE_CONSTRAINT_PRIMARYKEY, SQLITE_CONSTRAINT_UNIQUE
+            Ok(1555) | Ok(2067) => ErrorKind::UniqueViolation,
+
+            // SQLITE_CONSTRAINT_FOREIGNKEY
+            Ok(787) => ErrorKind::ForeignKeyViolation,
+
+            // SQLITE_CONSTRAINT_NOTNULL
+            Ok(1299) => ErrorKind::NotNullViolation,
+
+            // SQLITE_CONSTRAINT_CHECK
+            Ok(275) => ErrorKind::CheckViolation,
+
+            // SQLITE_BUSY_SNAPSHOT
+            Ok(517) => ErrorKind::SerializationFailure,
+
+            // SQLITE_BUSY, SQLITE_BUSY_RECOVERY, SQLITE_BUSY_TIMEOUT
+            Ok(5) | Ok(261) | Ok(773) => ErrorKind::LockTimeout,
+
+            _ => ErrorKind::Other,
+        }
+    }
+
     fn as_ref_err(&self) -> &(dyn StdError + Send + Sync + 'static) {
         self
     }
@@ -84,6 +109,7 @@ fn test_error_downcasting() {
         e => panic!("expected Error::Database, got {:?}", e),
     };
 
+    assert_eq!(db_err.kind(), ErrorKind::Other);
     assert_eq!(
         &db_err.downcast_ref::<SqliteError>().code,
         "SQLITE_ERR_SOMETHING"
@@ -1,24 +1,26 @@
 use futures_core::future::BoxFuture;
 
-use libsqlite3_sys::sqlite3_changes;
+use libsqlite3_sys::{
+    sqlite3_changes, sqlite3_last_insert_rowid, sqlite3_set_last_insert_rowid,
+    sqlite3_total_changes,
+};
 
 use crate::cursor::Cursor;
 use crate::describe::{Column, Describe};
-use crate::executor::{Execute, Executor, RefExecutor};
+use crate::executor::{Execute, Executor, QueryResult, RefExecutor, StatementResult};
+use crate::logging::QueryLogger;
 use crate::sqlite::cursor::SqliteCursor;
 use crate::sqlite::statement::{Statement, Step};
 use crate::sqlite::type_info::SqliteType;
 use crate::sqlite::{Sqlite, SqliteConnection, SqliteTypeInfo};
+use crate::statement::{check_connection, Statement as PreparedStatement};
 
 impl SqliteConnection {
-    pub(super) fn prepare(
+    pub(super) fn get_or_prepare(
         &mut self,
         query: &mut &str,
         persistent: bool,
     ) -> crate::Result<Option<usize>> {
-        // TODO: Revisit statement caching and allow cache expiration by using a
-        //       generational index
-
         if !persistent {
             // A non-persistent query will be immediately prepared and returned,
             // regardless of the current state of the cache
@@ -26,8 +28,10 @@ impl SqliteConnection {
             return Ok(None);
         }
 
-        if let Some(key) = self.statement_by_query.get(&**query) {
-            let statement = &mut self.statements[*key];
+        if let Some(&mut key) = self.statement_by_query.get_mut(&**query) {
+            let statement = self.statements[key]
+                .as_mut()
+                .expect("BUG: cached statement was finalized");
 
             // Adjust the passed in query string as if [string3_prepare]
             // did the tail parsing
@@ -37,7 +41,7 @@ impl SqliteConnection {
             // it to clear the bindings and its program state
             statement.reset();
 
-            return Ok(Some(*key));
+            return Ok(Some(key));
         }
 
         // Prepare a new statement object; ensuring to tell SQLite that this will be stored
@@ -46,28 +50,142 @@ impl SqliteConnection {
         let query_key = query.to_owned();
         let statement = Statement::new(self, query, true)?;
 
-        let key = self.statements.len();
+        let key = match self.vacant_statements.pop() {
+            Some(key) => key,
+            None => {
+                self.statements.push(None);
+                self.statements.len() - 1
+            }
+        };
+
+        // Statements evicted from the cache are finalized on drop and their slots re-used
+        for evicted in self.statement_by_query.insert(&query_key, key) {
+            self.statements[evicted] = None;
+            self.vacant_statements.push(evicted);
+        }
 
-        self.statement_by_query.insert(query_key, key);
-        self.statements.push(statement);
+        self.statements[key] = Some(statement);
 
         Ok(Some(key))
     }
 
-    // This is used for [affected_rows] in the public API.
-    fn changes(&mut self) -> u64 {
+    pub(super) fn clear_statement_cache(&mut self) {
+        self.statement_by_query.clear();
+        self.statements.clear();
+        self.vacant_statements.clear();
+    }
+
+    pub(super) fn prepare_statement(
+        &mut self,
+        sql: &str,
+    ) -> crate::Result<PreparedStatement<Sqlite>> {
+        // The statement is kept in the cache so that executing it does not prepare it again
+        let mut query = sql;
+        let key = self.get_or_prepare(&mut query, true)?;
+        let describe = self.describe_statement(key)?;
+
+        Ok(PreparedStatement::new(sql, self.id, describe))
+    }
+
+    fn describe_statement(&mut self, key: Option<usize>) -> crate::Result<Describe<Sqlite>> {
+        let statement = self.statement_mut(key);
+
+        // First let's attempt to describe what we can about parameter types
+        // Which happens to just be the count, heh
+        let num_params = statement.params();
+        let params = vec![None; num_params].into_boxed_slice();
+
+        // Next, collect (return) column types and names
+        let num_columns = statement.column_count();
+        let mut columns = Vec::with_capacity(num_columns);
+        for i in 0..num_columns {
+            let name = statement.column_name(i).to_owned();
+            let decl = statement.column_decltype(i);
+
+            let r#type = match decl {
+                None => None,
+                Some(decl) => match &*decl.to_ascii_lowercase() {
+                    "bool" | "boolean" => Some(SqliteType::Boolean),
+                    "clob" | "text" => Some(SqliteType::Text),
+                    "blob" => Some(SqliteType::Blob),
+                    "real" | "double" | "double precision" | "float" => Some(SqliteType::Float),
+                    decl if decl.contains("int") => Some(SqliteType::Integer),
+                    decl if decl.contains("char") => Some(SqliteType::Text),
+                    _ => None,
+                },
+            };
+
+            columns.push(Column {
+                name: Some(name.into()),
+                non_null: statement.column_not_null(i)?,
+                table_id: None,
+                type_info: r#type.map(|r#type| SqliteTypeInfo {
+                    r#type,
+                    affinity: None,
+                }),
+            })
+        }
+
+        Ok(Describe {
+            param_types: params,
+            result_columns: columns.into_boxed_slice(),
+        })
+    }
+
+    // The total number of changes and the ID of the last inserted row on this connection, read
+    // before a statement runs to tell its result apart from that of earlier statements.
+    //
+    // The ID is cleared, as an insert may well get the same ID as an earlier insert into
+    // another table. It is restored by `statement_result` if the statement inserts nothing.
+    fn change_state(&mut self) -> (i32, i64) {
+        // https://www.sqlite.org/c3ref/total_changes.html
+        // https://www.sqlite.org/c3ref/last_insert_rowid.html
+        // https://www.sqlite.org/c3ref/set_last_insert_rowid.html
+        unsafe {
+            let state = (
+                sqlite3_total_changes(self.handle()),
+                sqlite3_last_insert_rowid(self.handle()),
+            );
+
+            sqlite3_set_last_insert_rowid(self.handle(), 0);
+
+            state
+        }
+    }
+
+    // This is used for [QueryResult] in the public API. It must be called after every
+    // statement that `change_state` was called for, even if the statement failed.
+    fn statement_result(&mut self, before: (i32, i64)) -> StatementResult {
+        let total_changes = unsafe { sqlite3_total_changes(self.handle()) };
+        let last_insert_id = unsafe { sqlite3_last_insert_rowid(self.handle()) };
+
+        // Keeps `last_insert_rowid()` of the earlier insert for later statements
+        if last_insert_id == 0 {
+            unsafe { sqlite3_set_last_insert_rowid(self.handle(), before.1) };
+        }
+
         // Returns the number of rows modified, inserted or deleted by the most recently
-        // completed INSERT, UPDATE or DELETE statement.
+        // completed INSERT, UPDATE or DELETE statement. This is not reset by other statements,
+        // so it is only read if the statement changed rows.
 
         // https://www.sqlite.org/c3ref/changes.html
-        let changes = unsafe { sqlite3_changes(self.handle()) };
-        changes as u64
+        let rows_affected = if total_changes != before.0 {
+            unsafe { sqlite3_changes(self.handle()) as u64 }
+        } else {
+            0
+        };
+
+        StatementResult {
+            rows_affected,
+            last_insert_id: Some(last_insert_id).filter(|id| *id != 0),
+            command: None,
+        }
     }
 
     #[inline]
     pub(super) fn statement(&self, key: Option<usize>) -> &Statement {
         match key {
-            Some(key) => &self.statements[key],
+            Some(key) => self.statements[key].as_ref().unwrap(),
             None => self.statement.as_ref().unwrap(),
         }
     }
@@ -75,7 +193,7 @@ impl SqliteConnection {
     #[inline]
     pub(super) fn statement_mut(&mut self, key: Option<usize>) -> &mut Statement {
         match key {
-            Some(key) => &mut self.statements[key],
+            Some(key) => self.statements[key].as_mut().unwrap(),
             None => self.statement.as_mut().unwrap(),
         }
     }
@@ -87,33 +205,57 @@ impl Executor for SqliteConnection {
     fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
         &'c mut self,
         query: E,
-    ) -> BoxFuture<'e, crate::Result<u64>>
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, {
-            let (mut query, mut arguments) = query.into_parts();
+        let connection_id = query.connection_id();
+        let (mut query, mut arguments) = query.into_parts();
 
-            Box::pin(async move {
-                loop {
-                    let key = self.prepare(&mut query, arguments.is_some())?;
-                    let statement = self.statement_mut(key);
+        Box::pin(async move {
+            check_connection(connection_id, self.id)?;
 
-                    if let Some(arguments) = &mut arguments {
-                        statement.bind(arguments)?;
-                    }
+            let mut logger = QueryLogger::new(query, &self.log_settings);
+
+            if let Some(arguments) = &arguments {
+                logger.arguments(|| arguments.log_values());
+            }
+
+            let mut result = QueryResult::default();
 
-                    while let Step::Row = statement.step().await? {
+            loop {
+                let key = self.get_or_prepare(&mut query, arguments.is_some())?;
+                let statement = self.statement_mut(key);
+
+                if let Some(arguments) = &mut arguments {
+                    statement.bind(arguments)?;
+                }
+
+                let before = self.change_state();
+                let statement = self.statement_mut(key);
+
+                let done = loop {
+                    match statement.step().await {
                         // We only care about the rows modified; ignore
+                        Ok(Step::Row) => {}
+                        Ok(Step::Done) => break Ok(()),
+                        Err(error) => break Err(error),
                     }
+                };
 
-                    if query.is_empty() {
-                        break;
-                    }
+                let statement_result = self.statement_result(before);
+                done?;
+
+                result.push(statement_result);
+
+                if query.is_empty() {
+                    break;
                 }
+            }
 
-                Ok(self.changes())
-            })
+            logger.add_rows_affected(result.rows_affected());
+
+            Ok(result)
         })
     }
 
@@ -121,7 +263,7 @@ impl Executor for SqliteConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { SqliteCursor::from_connection(self, query) })
+        SqliteCursor::from_connection(self, query)
     }
 
     #[doc(hidden)]
@@ -134,49 +276,9 @@ impl Executor for SqliteConnection {
     {
         Box::pin(async move {
             let (mut query, _) = query.into_parts();
-            let key = self.prepare(&mut query, false)?;
-            let statement = self.statement_mut(key);
-
-            // First let's attempt to describe what we can about parameter types
-            // Which happens to just be the count, heh
-            let num_params = statement.params();
-            let params = vec![None; num_params].into_boxed_slice();
-
-            // Next, collect (return) column types and names
-            let num_columns = statement.column_count();
-            let mut columns = Vec::with_capacity(num_columns);
-            for i in 0..num_columns {
-                let name = statement.column_name(i).to_owned();
-                let decl = statement.column_decltype(i);
-
-                let r#type = match decl {
-                    None => None,
-                    Some(decl) => match &*decl.to_ascii_lowercase() {
-                        "bool" | "boolean" => Some(SqliteType::Boolean),
-                        "clob" | "text" => Some(SqliteType::Text),
-                        "blob" => Some(SqliteType::Blob),
-                        "real" | "double" | "double precision" | "float" => Some(SqliteType::Float),
-                        decl @ _ if decl.contains("int") => Some(SqliteType::Integer),
-                        decl @ _ if decl.contains("char") => Some(SqliteType::Text),
-                        _ => None,
-                    },
-                };
+            let key = self.get_or_prepare(&mut query, false)?;
 
-                columns.push(Column {
-                    name: Some(name.into()),
-                    non_null: statement.column_not_null(i)?,
-                    table_id: None,
-                    type_info: r#type.map(|r#type| SqliteTypeInfo {
-                        r#type,
-                        affinity: None,
-                    }),
-                })
-            }
-
-            Ok(Describe {
-                param_types: params,
-                result_columns: columns.into_boxed_slice(),
-            })
+            self.describe_statement(key)
         })
     }
 }
@@ -188,6 +290,6 @@ impl<'e> RefExecutor<'e> for &'e mut SqliteConnection {
     where
         E: Execute<'q, Self::Database>,
     {
-        log_execution!(query, { SqliteCursor::from_connection(self, query) })
+        SqliteCursor::from_connection(self, query)
     }
 }
@@ -7,10 +7,11 @@
 
 mod arguments;
 mod connection;
-mod cursor;
+pub(crate) mod cursor;
 mod database;
 mod error;
 mod executor;
+mod options;
 mod row;
 mod statement;
 mod type_info;
@@ -23,6 +24,7 @@ pub use connection::SqliteConnection;
 pub use cursor::SqliteCursor;
 pub use database::Sqlite;
 pub use error::SqliteError;
+pub use options::SqliteConnectOptions;
 pub use row::SqliteRow;
 pub use type_info::SqliteTypeInfo;
 pub use value::SqliteValue;
@@ -0,0 +1,158 @@
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::cache::DEFAULT_CAPACITY;
+use crate::logging::LogSettings;
+use crate::url::{parse_bool, parse_usize, Url};
+
+/// Options and flags which can be used to configure a SQLite connection.
+///
+/// A value of `SqliteConnectOptions` can be parsed from a connection URL of the form:
+///
+/// ```text
+/// sqlite://[path][?properties]
+/// ```
+///
+/// The path is a file path on the local machine, percent-decoded; `sqlite::memory:` opens
+/// an in-memory database.
+///
+/// ## Properties
+///
+/// |Parameter|Default|Description|
+/// |---------|-------|-----------|
+/// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. |
+/// | `create-if-missing` | `true` | Whether to create the database file if it does not exist. |
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use sqlx_core::connection::Connect;
+/// # use sqlx_core::sqlite::{SqliteConnectOptions, SqliteConnection};
+/// // URL connection string
+/// let conn = SqliteConnection::connect("sqlite://data.db").await?;
+///
+/// // Manually-constructed options
+/// let conn = SqliteConnection::connect_with(&SqliteConnectOptions::new()
+///     .filename("/var/lib/my app/data.db")
+///     .create_if_missing(false)
+/// ).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SqliteConnectOptions {
+    pub(crate) filename: PathBuf,
+    pub(crate) create_if_missing: bool,
+    pub(crate) statement_cache_capacity: usize,
+    pub(crate) log_settings: LogSettings,
+}
+
+impl Default for SqliteConnectOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SqliteConnectOptions {
+    /// Creates a new, default set of options ready for configuration.
+    ///
+    /// By default, an in-memory database is opened.
+    pub fn new() -> Self {
+        Self {
+            filename: PathBuf::from(":memory:"),
+            create_if_missing: true,
+            statement_cache_capacity: DEFAULT_CAPACITY,
+            log_settings: LogSettings::new(),
+        }
+    }
+
+    /// Sets the path of the database file to open.
+    ///
+    /// Unlike the path of a connection URL, this path is used as-is.
+    pub fn filename(mut self, path: impl AsRef<Path>) -> Self {
+        self.filename = path.as_ref().to_owned();
+        self
+    }
+
+    /// Sets whether the database file is created if it does not exist.
+    ///
+    /// Defaults to `true`.
+    pub fn create_if_missing(mut self, create: bool) -> Self {
+        self.create_if_missing = create;
+        self
+    }
+
+    /// Sets the capacity of the connection's statement cache in a number of stored
+    /// distinct statements.
+    ///
+    /// When the cache is full, the least recently used statement is finalized.
+    /// Defaults to `100`.
+    pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
+        self.statement_cache_capacity = capacity;
+        self
+    }
+
+    /// Sets how queries executed on the connection are logged.
+    pub fn log_settings(mut self, settings: LogSettings) -> Self {
+        self.log_settings = settings;
+        self
+    }
+}
+
+impl TryFrom<&'_ Url> for SqliteConnectOptions {
+    type Error = crate::Error;
+
+    fn try_from(url: &Url) -> crate::Result<Self> {
+        let mut options = Self::new();
+
+        // `sqlite::memory:` is the in-memory database rather than a file named `memory:`
+        if !url.as_str().starts_with("sqlite::memory:") {
+            options = options.filename(&*url.path_decoded());
+        }
+
+        if let Some(capacity) = url.param("statement-cache-capacity") {
+            options = options
+                .statement_cache_capacity(parse_usize("statement-cache-capacity", &capacity)?);
+        }
+
+        if let Some(flag) = url.param("create-if-missing") {
+            options = options.create_if_missing(parse_bool("create-if-missing", &flag)?);
+        }
+
+        Ok(options)
+    }
+}
+
+impl FromStr for SqliteConnectOptions {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let url = Url::try_from(s)?;
+
+        Self::try_from(&url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_url() {
+        let options: SqliteConnectOptions =
+            "sqlite://data/my%20db.sqlite?statement-cache-capacity=5&create-if-missing=false"
+                .parse()
+                .unwrap();
+
+        assert_eq!(options.filename, Path::new("data/my db.sqlite"));
+        assert_eq!(options.statement_cache_capacity, 5);
+        assert!(!options.create_if_missing);
+    }
+
+    #[test]
+    fn it_parses_in_memory_url() {
+        let options: SqliteConnectOptions = "sqlite::memory:".parse().unwrap();
+
+        assert_eq!(options.filename, Path::new(":memory:"));
+        assert!(options.create_if_missing);
+    }
+}
@@ -26,7 +26,7 @@ pub struct SqliteValue<'c> {
 
 impl<'c> SqliteValue<'c> {
     /// Returns true if the value should be intrepreted as NULL.
-    pub(super) fn is_null(&self) -> bool {
+    pub(crate) fn is_null(&self) -> bool {
         self.r#type().is_none()
     }
 
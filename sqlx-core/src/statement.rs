@@ -0,0 +1,130 @@
+//! Contains the `Statement` type, a handle to a statement prepared on a connection.
+
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::database::Database;
+use crate::describe::Describe;
+use crate::query::Query;
+use crate::query_as::QueryAs;
+
+pub use crate::describe::Column;
+
+/// A statement prepared with [`Connection::prepare`].
+///
+/// The statement describes the types of its parameters and the columns of its result and can
+/// be executed any number of times with [`query`] (or [`query_as`]) on the connection that
+/// prepared it. Executing it on any other connection returns
+/// [`Error::StatementConnectionMismatch`].
+///
+/// The prepared statement is held in the connection's statement cache; if it has since been
+/// evicted, it is transparently prepared again when executed.
+///
+/// ```rust,ignore
+/// let statement = conn.prepare("SELECT name FROM users WHERE id = $1").await?;
+///
+/// for id in ids {
+///     let row = statement.query().bind(id).fetch_one(&mut conn).await?;
+/// }
+/// ```
+///
+/// [`Connection::prepare`]: crate::connection::Connection::prepare
+/// [`query`]: Statement::query
+/// [`query_as`]: Statement::query_as
+/// [`Error::StatementConnectionMismatch`]: crate::Error::StatementConnectionMismatch
+pub struct Statement<DB>
+where
+    DB: Database,
+{
+    sql: String,
+    connection_id: u64,
+    param_types: Box<[Option<DB::TypeInfo>]>,
+    columns: Box<[Column<DB>]>,
+}
+
+impl<DB> Statement<DB>
+where
+    DB: Database,
+{
+    pub(crate) fn new(sql: &str, connection_id: u64, describe: Describe<DB>) -> Self {
+        Self {
+            sql: sql.to_owned(),
+            connection_id,
+            param_types: describe.param_types,
+            columns: describe.result_columns,
+        }
+    }
+
+    /// The SQL of this statement.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The expected types of the parameters of this statement.
+    ///
+    /// There is one entry per parameter; an entry is `None` if the database does not report
+    /// the type of that parameter.
+    pub fn param_types(&self) -> &[Option<DB::TypeInfo>] {
+        &self.param_types
+    }
+
+    /// The columns returned by this statement.
+    pub fn columns(&self) -> &[Column<DB>] {
+        &self.columns
+    }
+
+    /// Creates a query that executes this statement.
+    pub fn query(&self) -> Query<'_, DB> {
+        Query {
+            query: &self.sql,
+            arguments: Default::default(),
+            connection_id: Some(self.connection_id),
+            database: PhantomData,
+        }
+    }
+
+    /// Creates a query that executes this statement and maps its result to a concrete type
+    /// using [`FromRow`](crate::row::FromRow).
+    pub fn query_as<O>(&self) -> QueryAs<'_, DB, O> {
+        QueryAs {
+            query: &self.sql,
+            arguments: Default::default(),
+            connection_id: Some(self.connection_id),
+            database: PhantomData,
+            output: PhantomData,
+        }
+    }
+}
+
+impl<DB> Debug for Statement<DB>
+where
+    DB: Database,
+    DB::TypeInfo: Debug,
+    Column<DB>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Statement")
+            .field("sql", &self.sql)
+            .field("param_types", &self.param_types)
+            .field("columns", &self.columns)
+            .finish()
+    }
+}
+
+// Returns an identifier, unique within this process, for a new connection
+#[allow(dead_code)]
+pub(crate) fn next_connection_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+// Ensures that a query created from a [Statement] is executed on the connection that prepared it
+#[allow(dead_code)]
+pub(crate) fn check_connection(statement: Option<u64>, connection: u64) -> crate::Result<()> {
+    match statement {
+        Some(id) if id != connection => Err(crate::Error::StatementConnectionMismatch),
+        _ => Ok(()),
+    }
+}
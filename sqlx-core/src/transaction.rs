@@ -1,20 +1,23 @@
 use std::ops::{Deref, DerefMut};
+use std::panic;
 
 use futures_core::future::BoxFuture;
 
-use crate::connection::Connection;
+use crate::connection::{run_in_transaction, Connection};
 use crate::cursor::HasCursor;
 use crate::database::Database;
 use crate::describe::Describe;
-use crate::executor::{Execute, Executor, RefExecutor};
-use crate::runtime::spawn;
+use crate::executor::{Execute, Executor, QueryResult, RefExecutor};
+use crate::logging::LogSettings;
+use crate::statement::Statement;
 
 /// Represents an in-progress database transaction.
 ///
 /// A transaction ends with a call to [`commit`] or [`rollback`] in which the wrapped connection (
 /// or outer transaction) is returned. If neither are called before the transaction
-/// goes out-of-scope, [`rollback`] is called. In other words, [`rollback`] is called on `drop`
-/// if the transaction is still in-progress.
+/// goes out-of-scope, the whole transaction is rolled back. A connection from a [`Pool`] is
+/// rolled back by the pool before it is handed to the next acquirer (or closed if the rollback
+/// fails); any other connection is closed, which rolls back the transaction.
 ///
 /// ```rust,ignore
 /// // Acquire a new connection and immediately begin a transaction
@@ -31,6 +34,7 @@ use crate::runtime::spawn;
 ///
 /// [`commit`]: #method.commit
 /// [`rollback`]: #method.rollback
+/// [`Pool`]: crate::pool::Pool
 // Transaction<PoolConnection<PgConnection>>
 // Transaction<PgConnection>
 #[must_use = "transaction rolls back if not explicitly `.commit()`ed"]
@@ -46,6 +50,21 @@ impl<C> Transaction<C>
 where
     C: Connection,
 {
+    // Begins a transaction (not a save point) with the statements produced for the options
+    pub(crate) async fn with_options(
+        mut inner: C,
+        options: TransactionOptions,
+    ) -> crate::Result<Self> {
+        for statement in inner.begin_statements(&options) {
+            inner.execute(&*statement).await?;
+        }
+
+        Ok(Self {
+            inner: Some(inner),
+            depth: 1,
+        })
+    }
+
     pub(crate) async fn new(depth: u32, mut inner: C) -> crate::Result<Self> {
         if depth == 0 {
             inner.execute("BEGIN").await?;
@@ -67,6 +86,30 @@ where
         Transaction::new(self.depth, self).await
     }
 
+    /// Creates a new save point in the current transaction, like [`begin`](Self::begin).
+    ///
+    /// A save point cannot have an isolation level or access mode of its own, so any options
+    /// other than the default return an error.
+    pub async fn begin_with(
+        self,
+        options: TransactionOptions,
+    ) -> crate::Result<Transaction<Transaction<C>>> {
+        Connection::begin_with(self, options).await
+    }
+
+    /// Runs `f` in a save point of the current transaction.
+    ///
+    /// The save point is released if `f` returns `Ok`, and rolled back to if it returns `Err`
+    /// or panics (after which the panic is resumed). Either way, the current transaction
+    /// continues.
+    pub async fn transaction<'c, F, T>(&'c mut self, f: F) -> crate::Result<T>
+    where
+        F: for<'t> FnOnce(&'t mut Self) -> BoxFuture<'t, crate::Result<T>> + Send + 'c,
+        T: Send + 'c,
+    {
+        Connection::transaction(self, f).await
+    }
+
     /// Commits the current transaction or save point.
     /// Returns the inner connection or transaction.
     pub async fn commit(mut self) -> crate::Result<C> {
@@ -104,6 +147,65 @@ where
 
 const ERR_FINALIZED: &str = "(bug) transaction already finalized";
 
+/// The isolation level of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    #[allow(dead_code)]
+    pub(crate) fn as_sql(self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Options for a transaction started with [`Connection::begin_with`].
+///
+/// The default options start a transaction like [`Connection::begin`]. How the options are
+/// applied depends on the database:
+///
+///  * **Postgres** begins the transaction with `BEGIN ISOLATION LEVEL .. READ ONLY DEFERRABLE`.
+///  * **MySQL** sets the isolation level with `SET TRANSACTION ISOLATION LEVEL ..` and begins
+///    the transaction with `START TRANSACTION READ ONLY`. `deferrable` is ignored.
+///  * **SQLite** transactions are always serializable; `BEGIN IMMEDIATE` is used to take the
+///    write lock up front if an isolation level is set, or `BEGIN EXCLUSIVE` if it is
+///    [`Serializable`](IsolationLevel::Serializable). Read-only transactions use `BEGIN`.
+///    `deferrable` is ignored.
+///
+/// ```rust,ignore
+/// let tx = conn
+///     .begin_with(TransactionOptions {
+///         isolation: Some(IsolationLevel::Serializable),
+///         read_only: true,
+///         deferrable: true,
+///     })
+///     .await?;
+/// ```
+///
+/// [`Connection::begin_with`]: crate::connection::Connection::begin_with
+/// [`Connection::begin`]: crate::connection::Connection::begin
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionOptions {
+    /// The isolation level of the transaction, or `None` for the database default.
+    pub isolation: Option<IsolationLevel>,
+
+    /// Whether the transaction may only read.
+    pub read_only: bool,
+
+    /// Whether a serializable, read-only transaction may wait to start until it can run
+    /// without the risk of a serialization failure.
+    pub deferrable: bool,
+}
+
 impl<C> Deref for Transaction<C>
 where
     C: Connection,
@@ -152,10 +254,106 @@ where
         })
     }
 
+    // A transaction nests a save point instead of beginning another transaction
+    fn begin(self) -> BoxFuture<'static, crate::Result<Transaction<Self>>> {
+        Box::pin(Transaction::new(self.depth, self))
+    }
+
+    fn begin_with(
+        self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'static, crate::Result<Transaction<Self>>> {
+        Box::pin(async move {
+            if options != TransactionOptions::default() {
+                return Err(crate::Error::Configuration(
+                    "transaction options cannot be applied to a save point".into(),
+                ));
+            }
+
+            Transaction::new(self.depth, self).await
+        })
+    }
+
+    fn transaction<'c, F, T>(&'c mut self, f: F) -> BoxFuture<'c, crate::Result<T>>
+    where
+        F: for<'t> FnOnce(&'t mut Self) -> BoxFuture<'t, crate::Result<T>> + Send + 'c,
+        T: Send + 'c,
+    {
+        Box::pin(async move {
+            let depth = self.depth;
+            let savepoint = format!("_sqlx_savepoint_{}", depth);
+
+            // Save points begun while `f` runs are nested in this one
+            self.depth += 1;
+
+            let result = run_in_transaction(
+                self,
+                vec![format!("SAVEPOINT {}", savepoint)],
+                &format!("RELEASE SAVEPOINT {}", savepoint),
+                &format!("ROLLBACK TO SAVEPOINT {}", savepoint),
+                f,
+            )
+            .await;
+
+            self.depth = depth;
+
+            result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+        })
+    }
+
     #[inline]
     fn ping(&mut self) -> BoxFuture<'_, crate::Result<()>> {
         self.deref_mut().ping()
     }
+
+    #[inline]
+    fn cached_statements_size(&self) -> usize {
+        self.deref().cached_statements_size()
+    }
+
+    #[inline]
+    fn set_statement_cache_capacity(&mut self, capacity: usize) {
+        self.deref_mut().set_statement_cache_capacity(capacity)
+    }
+
+    #[inline]
+    fn clear_cached_statements(&mut self) -> BoxFuture<'_, crate::Result<()>> {
+        self.deref_mut().clear_cached_statements()
+    }
+
+    #[inline]
+    fn log_settings(&self) -> &LogSettings {
+        self.deref().log_settings()
+    }
+
+    #[inline]
+    fn set_log_settings(&mut self, settings: LogSettings) {
+        self.deref_mut().set_log_settings(settings)
+    }
+
+    #[inline]
+    fn prepare<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, crate::Result<Statement<Self::Database>>> {
+        self.deref_mut().prepare(sql)
+    }
+
+    #[inline]
+    fn is_broken(&self) -> bool {
+        self.deref().is_broken()
+    }
+
+    #[inline]
+    fn in_transaction(&self) -> bool {
+        self.deref().in_transaction()
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    fn begin_statements(&self, options: &TransactionOptions) -> Vec<String> {
+        self.deref().begin_statements(options)
+    }
 }
 
 impl<DB, C> Executor for Transaction<C>
@@ -168,7 +366,7 @@ where
     fn execute<'e, 'q: 'e, 'c: 'e, E: 'e>(
         &'c mut self,
         query: E,
-    ) -> BoxFuture<'e, crate::Result<u64>>
+    ) -> BoxFuture<'e, crate::Result<QueryResult>>
     where
         E: Execute<'q, Self::Database>,
     {
@@ -214,12 +412,9 @@ where
     C: Connection,
 {
     fn drop(&mut self) {
-        if self.depth > 0 {
-            if let Some(inner) = self.inner.take() {
-                spawn(async move {
-                    let _ = inner.close().await;
-                });
-            }
-        }
+        // The connection is not closed here: a pooled connection is rolled back by the pool
+        // before it is acquired again, and the database rolls back the transaction of any
+        // other connection when it is dropped
+        drop(self.inner.take());
     }
 }
@@ -29,11 +29,15 @@ impl<'s> TryFrom<&'s String> for Url {
 }
 
 impl Url {
-    #[allow(dead_code)]
     pub(crate) fn as_str(&self) -> &str {
         self.0.as_str()
     }
 
+    #[allow(dead_code)]
+    pub(crate) fn scheme(&self) -> &str {
+        self.0.scheme()
+    }
+
     pub fn host(&self) -> Option<&str> {
         match self.0.host_str()? {
             "" => None,
@@ -75,13 +79,13 @@ impl Url {
         }
     }
 
-    /// Undo URL percent-encoding and return [authority]path[query]
+    /// Undo URL percent-encoding and return [authority]path
     ///
     /// Mostly a hack to fix special-character handling for SQLite as its connection string is a
     /// file path and not _really_ a URL
     pub fn path_decoded(&self) -> Cow<str> {
-        // omit scheme (e.g. `sqlite://`, `mysql://`)
-        let url_str = &self.0.as_str()[self.0.scheme().len()..]
+        // omit scheme (e.g. `sqlite://`, `mysql://`) and query parameters
+        let url_str = &self.0[..url::Position::AfterPath][self.0.scheme().len()..]
             .trim_start_matches(':')
             .trim_start_matches("//");
 
@@ -101,6 +105,11 @@ impl Url {
         }
     }
 
+    #[allow(dead_code)]
+    pub(crate) fn params(&self) -> impl Iterator<Item = (Cow<str>, Cow<str>)> {
+        self.0.query_pairs()
+    }
+
     pub fn param(&self, key: &str) -> Option<Cow<str>> {
         self.0
             .query_pairs()
@@ -108,6 +117,33 @@ impl Url {
     }
 }
 
+// Parses a boolean query parameter
+#[allow(dead_code)]
+pub(crate) fn parse_bool(key: &str, value: &str) -> crate::Result<bool> {
+    match &*value.to_ascii_lowercase() {
+        "true" | "1" | "on" | "yes" => Ok(true),
+        "false" | "0" | "off" | "no" => Ok(false),
+
+        _ => Err(crate::Error::Configuration(
+            format!("expected a boolean for `{}`, got {:?}", key, value).into(),
+        )),
+    }
+}
+
+// Parses a numeric query parameter
+#[allow(dead_code)]
+pub(crate) fn parse_usize(key: &str, value: &str) -> crate::Result<usize> {
+    value.parse().map_err(|_| {
+        crate::Error::Configuration(
+            format!(
+                "expected a non-negative integer for `{}`, got {:?}",
+                key, value
+            )
+            .into(),
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
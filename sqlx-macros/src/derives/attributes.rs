@@ -1,7 +1,9 @@
 use proc_macro2::Ident;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{Attribute, DeriveInput, Field, Lit, Meta, MetaNameValue, NestedMeta, Variant};
+use syn::{
+    Attribute, DeriveInput, Field, Lit, Meta, MetaNameValue, NestedMeta, Path, Type, Variant,
+};
 
 macro_rules! assert_attribute {
     ($e:expr, $err:expr, $input:expr) => {
@@ -30,6 +32,11 @@ macro_rules! try_set {
 pub enum RenameAll {
     LowerCase,
     SnakeCase,
+    UpperCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    CamelCase,
+    PascalCase,
 }
 
 pub struct SqlxContainerAttributes {
@@ -41,6 +48,11 @@ pub struct SqlxContainerAttributes {
 
 pub struct SqlxChildAttributes {
     pub rename: Option<String>,
+    pub default: bool,
+    pub flatten: bool,
+    pub skip: bool,
+    pub try_from: Option<Type>,
+    pub with: Option<Path>,
 }
 
 pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContainerAttributes> {
@@ -70,6 +82,11 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                                 let val = match &*val.value() {
                                     "lowercase" => RenameAll::LowerCase,
                                     "snake_case" => RenameAll::SnakeCase,
+                                    "UPPERCASE" => RenameAll::UpperCase,
+                                    "SCREAMING_SNAKE_CASE" => RenameAll::ScreamingSnakeCase,
+                                    "kebab-case" => RenameAll::KebabCase,
+                                    "camelCase" => RenameAll::CamelCase,
+                                    "PascalCase" => RenameAll::PascalCase,
 
                                     _ => fail!(meta, "unexpected value for rename_all"),
                                 };
@@ -114,6 +131,11 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
 
 pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttributes> {
     let mut rename = None;
+    let mut default = None;
+    let mut flatten = None;
+    let mut skip = None;
+    let mut try_from = None;
+    let mut with = None;
 
     for attr in input {
         let meta = attr
@@ -131,6 +153,32 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                                 ..
                             }) if path.is_ident("rename") => try_set!(rename, val.value(), value),
 
+                            Meta::Path(p) if p.is_ident("default") => {
+                                try_set!(default, true, value)
+                            }
+
+                            Meta::Path(p) if p.is_ident("flatten") => {
+                                try_set!(flatten, true, value)
+                            }
+
+                            Meta::Path(p) if p.is_ident("skip") => try_set!(skip, true, value),
+
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("try_from") => {
+                                try_set!(try_from, val.parse::<Type>()?, value)
+                            }
+
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("with") => {
+                                try_set!(with, val.parse::<Path>()?, value)
+                            }
+
                             u => fail!(u, "unexpected attribute"),
                         },
                         u => fail!(u, "unexpected attribute"),
@@ -141,7 +189,14 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
         }
     }
 
-    Ok(SqlxChildAttributes { rename })
+    Ok(SqlxChildAttributes {
+        rename,
+        default: default.unwrap_or(false),
+        flatten: flatten.unwrap_or(false),
+        skip: skip.unwrap_or(false),
+        try_from,
+        with,
+    })
 }
 
 pub fn check_transparent_attributes(input: &DeriveInput, field: &Field) -> syn::Result<()> {
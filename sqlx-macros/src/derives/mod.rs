@@ -10,7 +10,7 @@ pub(crate) use r#type::expand_derive_type;
 pub(crate) use row::expand_derive_from_row;
 
 use self::attributes::RenameAll;
-use heck::SnakeCase;
+use heck::{CamelCase, KebabCase, MixedCase, ShoutySnakeCase, SnakeCase};
 use std::iter::FromIterator;
 use syn::DeriveInput;
 
@@ -32,5 +32,10 @@ pub(crate) fn rename_all(s: &str, pattern: RenameAll) -> String {
     match pattern {
         RenameAll::LowerCase => s.to_lowercase(),
         RenameAll::SnakeCase => s.to_snake_case(),
+        RenameAll::UpperCase => s.to_uppercase(),
+        RenameAll::ScreamingSnakeCase => s.to_shouty_snake_case(),
+        RenameAll::KebabCase => s.to_kebab_case(),
+        RenameAll::CamelCase => s.to_mixed_case(),
+        RenameAll::PascalCase => s.to_camel_case(),
     }
 }
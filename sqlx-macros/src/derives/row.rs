@@ -1,11 +1,12 @@
-use proc_macro2::Span;
+use proc_macro2::{Ident, Span};
 use quote::quote;
 use syn::{
-    parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Field,
-    Fields, FieldsNamed, Lifetime, Stmt,
+    parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Expr, Field,
+    Fields, FieldsNamed, FieldsUnnamed, Lifetime, Stmt, Type,
 };
 
-use super::attributes::parse_child_attributes;
+use super::attributes::{parse_child_attributes, parse_container_attributes};
+use super::rename_all;
 
 pub fn expand_derive_from_row(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     match &input.data {
@@ -15,12 +16,9 @@ pub fn expand_derive_from_row(input: &DeriveInput) -> syn::Result<proc_macro2::T
         }) => expand_derive_from_row_struct(input, named),
 
         Data::Struct(DataStruct {
-            fields: Fields::Unnamed(_),
+            fields: Fields::Unnamed(FieldsUnnamed { unnamed, .. }),
             ..
-        }) => Err(syn::Error::new_spanned(
-            input,
-            "tuple structs are not supported",
-        )),
+        }) => expand_derive_from_row_struct_unnamed(input, unnamed),
 
         Data::Struct(DataStruct {
             fields: Fields::Unit,
@@ -41,6 +39,253 @@ fn expand_derive_from_row_struct(
     fields: &Punctuated<Field, Comma>,
 ) -> syn::Result<proc_macro2::TokenStream> {
     let ident = &input.ident;
+    let container_attributes = parse_container_attributes(&input.attrs)?;
+
+    let mut reads: Vec<Stmt> = Vec::with_capacity(fields.len());
+    let mut names = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        let id = field.ident.as_ref().unwrap();
+        let attributes = parse_child_attributes(&field.attrs)?;
+        let ty = &field.ty;
+
+        let column = match attributes.rename {
+            Some(rename) => rename,
+            None => {
+                let name = id.to_string().trim_start_matches("r#").to_owned();
+
+                match container_attributes.rename_all {
+                    Some(pattern) => rename_all(&name, pattern),
+                    None => name,
+                }
+            }
+        };
+
+        let expr = read_field(
+            field,
+            parse_quote!(#column),
+            parse_quote!(sqlx::Error::ColumnNotFound(_)),
+        )?;
+
+        reads.push(parse_quote!(
+            let #id: #ty = #expr;
+        ));
+
+        names.push(id);
+    }
+
+    expand_from_row_impl(
+        input,
+        fields,
+        ColumnIndex::Name,
+        quote!(
+            #(#reads)*
+
+            Ok(#ident {
+                #(#names),*
+            })
+        ),
+    )
+}
+
+fn expand_derive_from_row_struct_unnamed(
+    input: &DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+
+    let mut reads: Vec<Stmt> = Vec::with_capacity(fields.len());
+    let mut names = Vec::with_capacity(fields.len());
+
+    // Fields are decoded from consecutive columns; skipped fields do not take up a column
+    let mut index = 0_usize;
+
+    for (i, field) in fields.iter().enumerate() {
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        if attributes.rename.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "#[sqlx(rename = ..)] is not supported on tuple struct fields",
+            ));
+        }
+
+        if attributes.flatten {
+            return Err(syn::Error::new_spanned(
+                field,
+                "#[sqlx(flatten)] is not supported on tuple struct fields",
+            ));
+        }
+
+        let id = Ident::new(&format!("__field{}", i), Span::call_site());
+        let ty = &field.ty;
+
+        let expr = read_field(
+            field,
+            parse_quote!(#index),
+            parse_quote!(sqlx::Error::ColumnIndexOutOfBounds { .. }),
+        )?;
+
+        if !attributes.skip {
+            index += 1;
+        }
+
+        reads.push(parse_quote!(
+            let #id: #ty = #expr;
+        ));
+
+        names.push(id);
+    }
+
+    expand_from_row_impl(
+        input,
+        fields,
+        ColumnIndex::Position,
+        quote!(
+            #(#reads)*
+
+            Ok(#ident(
+                #(#names),*
+            ))
+        ),
+    )
+}
+
+// How the columns of the row are looked up
+enum ColumnIndex {
+    Name,
+    Position,
+}
+
+// Builds the expression that produces the value of a field from the row
+fn read_field(field: &Field, column: Expr, missing: syn::Pat) -> syn::Result<Expr> {
+    let attributes = parse_child_attributes(&field.attrs)?;
+    let ty = &field.ty;
+
+    if [attributes.skip, attributes.flatten, attributes.default]
+        .iter()
+        .filter(|set| **set)
+        .count()
+        > 1
+    {
+        return Err(syn::Error::new_spanned(
+            field,
+            "only one of #[sqlx(skip)], #[sqlx(flatten)] and #[sqlx(default)] may be used",
+        ));
+    }
+
+    let converted = attributes.try_from.is_some() || attributes.with.is_some();
+
+    if converted && (attributes.skip || attributes.flatten) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "#[sqlx(try_from = ..)] and #[sqlx(with = ..)] cannot be used with #[sqlx(skip)] or #[sqlx(flatten)]",
+        ));
+    }
+
+    // A failed conversion is reported as a decode error of the column
+    let map_err = quote!(|error| sqlx::Error::ColumnDecode {
+        index: format!("{:?}", #column),
+        source: error.into(),
+    });
+
+    let value: Expr = match (&attributes.try_from, &attributes.with) {
+        (None, None) => parse_quote!(row.try_get(#column)),
+
+        (Some(from), None) => parse_quote!(
+            row.try_get::<#from, _>(#column).and_then(|value| {
+                <#ty as ::std::convert::TryFrom<#from>>::try_from(value).map_err(#map_err)
+            })
+        ),
+
+        (Some(from), Some(with)) => parse_quote!(
+            row.try_get::<#from, _>(#column).and_then(|value| #with(value).map_err(#map_err))
+        ),
+
+        // The column is decoded as the type the function takes
+        (None, Some(with)) => parse_quote!(
+            row.try_get(#column).and_then(|value| #with(value).map_err(#map_err))
+        ),
+    };
+
+    Ok(if attributes.skip {
+        parse_quote!(::std::default::Default::default())
+    } else if attributes.flatten {
+        parse_quote!(sqlx::row::FromRow::from_row(row)?)
+    } else if attributes.default {
+        parse_quote!(match #value {
+            Err(#missing) => ::std::default::Default::default(),
+            value => value?,
+        })
+    } else {
+        parse_quote!(#value?)
+    })
+}
+
+fn expand_from_row_impl(
+    input: &DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+    index: ColumnIndex,
+    body: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut infers_types = false;
+
+    for field in fields {
+        let attributes = parse_child_attributes(&field.attrs)?;
+        infers_types |= attributes.with.is_some() && attributes.try_from.is_none();
+    }
+
+    // The type a `with` function takes can only be inferred for a specific row type, as it
+    // cannot be named in the bounds of an implementation for any row
+    if !infers_types {
+        return expand_from_row_impl_for(input, fields, &index, &body, None);
+    }
+
+    let rows = database_rows();
+
+    if rows.is_empty() {
+        return Err(syn::Error::new_spanned(
+            input,
+            "#[sqlx(with = ..)] without #[sqlx(try_from = ..)] requires a database feature",
+        ));
+    }
+
+    let impls = rows
+        .iter()
+        .map(|row| expand_from_row_impl_for(input, fields, &index, &body, Some(row)))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote!(#(#impls)*))
+}
+
+// The row types of the enabled databases, without their lifetime
+fn database_rows() -> Vec<proc_macro2::TokenStream> {
+    let mut rows = Vec::new();
+
+    if cfg!(feature = "postgres") {
+        rows.push(quote!(sqlx::postgres::PgRow));
+    }
+
+    if cfg!(feature = "mysql") {
+        rows.push(quote!(sqlx::mysql::MySqlRow));
+    }
+
+    if cfg!(feature = "sqlite") {
+        rows.push(quote!(sqlx::sqlite::SqliteRow));
+    }
+
+    rows
+}
+
+// Implements `FromRow` for the given row type, or for any row if there is none
+fn expand_from_row_impl_for(
+    input: &DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+    index: &ColumnIndex,
+    body: &proc_macro2::TokenStream,
+    database_row: Option<&proc_macro2::TokenStream>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
 
     let generics = &input.generics;
 
@@ -53,51 +298,87 @@ fn expand_derive_from_row_struct(
     let (_, ty_generics, _) = generics.split_for_impl();
 
     let mut generics = generics.clone();
-    generics
-        .params
-        .insert(0, parse_quote!(R: sqlx::Row<#lifetime>));
+
+    let row: Type = match database_row {
+        Some(row) => parse_quote!(#row<#lifetime>),
+
+        None => {
+            generics
+                .params
+                .insert(0, parse_quote!(R: sqlx::Row<#lifetime>));
+
+            parse_quote!(R)
+        }
+    };
 
     if provided {
         generics.params.insert(0, parse_quote!(#lifetime));
     }
 
+    let database: Type = parse_quote!(<#row as sqlx::Row<#lifetime>>::Database);
     let predicates = &mut generics.make_where_clause().predicates;
 
-    predicates.push(parse_quote!(&#lifetime str: sqlx::row::ColumnIndex<#lifetime, R>));
+    match index {
+        ColumnIndex::Name => {
+            predicates.push(parse_quote!(&#lifetime str: sqlx::row::ColumnIndex<#lifetime, #row>))
+        }
+
+        ColumnIndex::Position => {
+            predicates.push(parse_quote!(usize: sqlx::row::ColumnIndex<#lifetime, #row>))
+        }
+    }
 
     for field in fields {
+        let attributes = parse_child_attributes(&field.attrs)?;
         let ty = &field.ty;
 
-        predicates.push(parse_quote!(#ty: sqlx::decode::Decode<#lifetime, R::Database>));
-        predicates.push(parse_quote!(#ty: sqlx::types::Type<R::Database>));
-    }
+        if attributes.skip {
+            predicates.push(parse_quote!(#ty: ::std::default::Default));
+        } else if attributes.flatten {
+            predicates.push(parse_quote!(#ty: sqlx::row::FromRow<#lifetime, #row>));
+        } else {
+            // The column is decoded as the `try_from` type when it is converted, or as the type
+            // the `with` function takes, which is inferred
+            let decoded = match (&attributes.try_from, &attributes.with) {
+                (Some(from), _) => Some(from),
+                (None, Some(_)) => None,
+                (None, None) => Some(ty),
+            };
 
-    let (impl_generics, _, where_clause) = generics.split_for_impl();
+            if let Some(decoded) = decoded {
+                predicates.push(parse_quote!(#decoded: sqlx::decode::Decode<#lifetime, #database>));
+                predicates.push(parse_quote!(#decoded: sqlx::types::Type<#database>));
+            }
 
-    let reads = fields.iter().filter_map(|field| -> Option<Stmt> {
-        let id = &field.ident.as_ref()?;
-        let attributes = parse_child_attributes(&field.attrs).unwrap();
-        let id_s = match attributes.rename {
-            Some(rename) => rename,
-            None => id.to_string().trim_start_matches("r#").to_owned(),
-        };
-        let ty = &field.ty;
+            if let (Some(from), None) = (&attributes.try_from, &attributes.with) {
+                predicates.push(parse_quote!(#ty: ::std::convert::TryFrom<#from>));
+                predicates.push(parse_quote!(
+                    <#ty as ::std::convert::TryFrom<#from>>::Error:
+                        ::std::convert::Into<Box<dyn ::std::error::Error + Send + Sync>>
+                ));
+            }
+
+            if attributes.default {
+                predicates.push(parse_quote!(#ty: ::std::default::Default));
+            }
+        }
+    }
 
-        Some(parse_quote!(
-            let #id: #ty = row.try_get(#id_s)?;
-        ))
+    // The methods of `Row` are only in scope through the bound on `R`
+    let import = database_row.map(|_| {
+        quote!(
+            use sqlx::row::Row as _;
+        )
     });
 
-    let names = fields.iter().map(|field| &field.ident);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
 
     Ok(quote!(
-        impl #impl_generics sqlx::row::FromRow<#lifetime, R> for #ident #ty_generics #where_clause {
-            fn from_row(row: &R) -> sqlx::Result<Self> {
-                #(#reads)*
+        impl #impl_generics sqlx::row::FromRow<#lifetime, #row> for #ident #ty_generics #where_clause {
+            fn from_row(row: &#row) -> sqlx::Result<Self> {
+                #import
 
-                Ok(#ident {
-                    #(#names),*
-                })
+                #body
             }
         }
     ))
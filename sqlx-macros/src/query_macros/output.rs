@@ -9,6 +9,7 @@ use crate::database::DatabaseExt;
 use std::fmt::{self, Display, Formatter};
 
 pub struct RustColumn {
+    pub(super) name: String,
     pub(super) ident: Ident,
     pub(super) type_: TokenStream,
 }
@@ -92,7 +93,11 @@ pub fn columns_to_rust<DB: DatabaseExt>(describe: &Describe<DB>) -> crate::Resul
                 type_ = quote! { Option<#type_> };
             }
 
-            Ok(RustColumn { ident, type_ })
+            Ok(RustColumn {
+                name: name.to_owned(),
+                ident,
+                type_,
+            })
         })
         .collect::<crate::Result<Vec<_>>>()
 }
@@ -108,18 +113,19 @@ pub fn quote_query_as<DB: DatabaseExt>(
         |(
             i,
             &RustColumn {
+                ref name,
                 ref ident,
                 ref type_,
-                ..
             },
         )| {
             // For "checked" queries, the macro checks these at compile time and using "try_get"
             // would also perform pointless runtime checks
 
+            // Errors name the column rather than its position
             if checked {
-                quote!( #ident: row.try_get_unchecked::<#type_, _>(#i).try_unwrap_optional()? )
+                quote!( #ident: row.try_get_unchecked::<#type_, _>(#i).try_unwrap_optional().map_err(|e| e.in_column(#name))? )
             } else {
-                quote!( #ident: row.try_get_unchecked(#i)? )
+                quote!( #ident: row.try_get_unchecked(#i).map_err(|e| e.in_column(#name))? )
             }
         },
     );
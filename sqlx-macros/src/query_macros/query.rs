@@ -53,6 +53,7 @@ where
             |&output::RustColumn {
                  ref ident,
                  ref type_,
+                 ..
              }| quote!(#ident: #type_,),
         )
         .collect::<TokenStream>();
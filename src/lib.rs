@@ -4,12 +4,14 @@ pub use sqlx_core::arguments;
 pub use sqlx_core::connection::{Connect, Connection};
 pub use sqlx_core::cursor::{self, Cursor};
 pub use sqlx_core::database::{self, Database};
-pub use sqlx_core::executor::{self, Execute, Executor};
+pub use sqlx_core::executor::{self, Execute, Executor, QueryResult};
+pub use sqlx_core::logging;
 pub use sqlx_core::pool::{self, Pool};
 pub use sqlx_core::query::{self, query, Query};
 pub use sqlx_core::query_as::{query_as, QueryAs};
 pub use sqlx_core::row::{self, FromRow, Row};
-pub use sqlx_core::transaction::Transaction;
+pub use sqlx_core::statement::{self, Statement};
+pub use sqlx_core::transaction::{IsolationLevel, Transaction, TransactionOptions};
 pub use sqlx_core::value;
 
 #[doc(hidden)]
@@ -19,7 +21,7 @@ pub use sqlx_core::describe;
 pub use sqlx_core::types::{self, Type};
 
 #[doc(inline)]
-pub use sqlx_core::error::{self, Error, Result};
+pub use sqlx_core::error::{self, Error, ErrorKind, Result};
 
 #[cfg(feature = "mysql")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mysql")))]
@@ -33,6 +35,13 @@ pub use sqlx_core::postgres::{self, PgConnection, PgPool, Postgres};
 #[cfg_attr(docsrs, doc(cfg(feature = "sqlite")))]
 pub use sqlx_core::sqlite::{self, Sqlite, SqliteConnection, SqlitePool};
 
+#[cfg(all(
+    feature = "any",
+    any(feature = "postgres", feature = "mysql", feature = "sqlite")
+))]
+#[cfg_attr(docsrs, doc(cfg(feature = "any")))]
+pub use sqlx_core::any::{self, Any, AnyConnection, AnyPool};
+
 #[cfg(feature = "macros")]
 #[doc(hidden)]
 pub extern crate sqlx_macros;
@@ -29,6 +29,14 @@ impl<T> ResultExt<Option<T>> for crate::Result<T> {
                 }
             }
 
+            Err(Error::ColumnDecode { index, source }) => {
+                if let Some(UnexpectedNullError) = source.downcast_ref() {
+                    Ok(None)
+                } else {
+                    Err(Error::ColumnDecode { index, source })
+                }
+            }
+
             Err(e) => Err(e),
         }
     }
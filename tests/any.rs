@@ -0,0 +1,119 @@
+use futures::TryStreamExt;
+use sqlx::any::AnyQueryAs;
+use sqlx::{Any, AnyConnection, AnyPool, Connect, Connection, Cursor, Executor, Row};
+use sqlx_test::new;
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_connects() -> anyhow::Result<()> {
+    Ok(new::<Any>().await?.ping().await?)
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_rejects_an_unknown_scheme() -> anyhow::Result<()> {
+    let res = AnyConnection::connect("oracle://localhost/db").await;
+
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_binds_and_decodes_values() -> anyhow::Result<()> {
+    let mut conn = new::<Any>().await?;
+
+    let mut cursor = sqlx::query("SELECT ?, ?, ?")
+        .bind(10_i64)
+        .bind("'?' is not a placeholder here")
+        .bind(None::<i64>)
+        .fetch(&mut conn);
+
+    let row = cursor.next().await?.unwrap();
+
+    assert_eq!(row.get::<i64, _>(0), 10);
+    assert_eq!(row.get::<String, _>(1), "'?' is not a placeholder here");
+    assert_eq!(row.get::<Option<i64>, _>(2), None);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_executes_with_placeholders() -> anyhow::Result<()> {
+    let mut conn = new::<Any>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE any_users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    for (id, name) in &[(1_i32, "alice"), (2, "bob")] {
+        let result = sqlx::query("INSERT INTO any_users (id, name) VALUES (?, ?)")
+            .bind(id)
+            .bind(name)
+            .execute(&mut conn)
+            .await?;
+
+        assert_eq!(result.rows_affected(), 1);
+    }
+
+    let mut cursor = sqlx::query("SELECT name FROM any_users WHERE id > ? ORDER BY id")
+        .bind(0_i32)
+        .fetch(&mut conn);
+
+    let mut names = Vec::new();
+
+    while let Some(row) = cursor.next().await? {
+        names.push(row.get::<String, _>("name"));
+    }
+
+    assert_eq!(names, vec!["alice", "bob"]);
+
+    let (name,): (String,) = sqlx::query_as("SELECT name FROM any_users WHERE id = ?")
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(name, "bob");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_prepares_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Any>().await?;
+
+    let statement = conn.prepare("SELECT ?").await?;
+
+    assert_eq!(statement.sql(), "SELECT ?");
+    assert_eq!(statement.param_types().len(), 1);
+
+    let value: String = statement
+        .query()
+        .bind("hello")
+        .try_map(|row: sqlx::any::AnyRow| row.try_get(0))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, "hello");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fetches_from_a_pool() -> anyhow::Result<()> {
+    let pool = AnyPool::new(&dotenv::var("DATABASE_URL")?).await?;
+
+    let values = sqlx::query("SELECT ?")
+        .bind(7_i64)
+        .map(|row: sqlx::any::AnyRow| row.get::<i64, _>(0))
+        .fetch(&pool)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    assert_eq!(values, vec![7]);
+
+    Ok(())
+}
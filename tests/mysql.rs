@@ -1,5 +1,8 @@
 use futures::TryStreamExt;
-use sqlx::{mysql::MySqlQueryAs, Connection, Executor, MySql, MySqlPool};
+use sqlx::{
+    mysql::{MySqlConnectOptions, MySqlQueryAs},
+    Connect, Connection, Executor, MySql, MySqlConnection, MySqlPool,
+};
 use sqlx_test::new;
 use std::time::Duration;
 
@@ -15,10 +18,10 @@ async fn it_drops_results_in_affected_rows() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
 
     // ~1800 rows should be iterated and dropped
-    let affected = conn.execute("select * from mysql.time_zone").await?;
+    let result = conn.execute("select * from mysql.time_zone").await?;
 
     // In MySQL, rows being returned isn't enough to flag it as an _affected_ row
-    assert_eq!(0, affected);
+    assert_eq!(0, result.rows_affected());
 
     Ok(())
 }
@@ -37,12 +40,12 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)
         .await?;
 
     for index in 1..=10_i32 {
-        let cnt = sqlx::query("INSERT INTO users (id) VALUES (?)")
+        let result = sqlx::query("INSERT INTO users (id) VALUES (?)")
             .bind(index)
             .execute(&mut conn)
             .await?;
 
-        assert_eq!(cnt, 1);
+        assert_eq!(result.rows_affected(), 1);
     }
 
     let sum: i32 = sqlx::query_as("SELECT id FROM users")
@@ -55,6 +58,32 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reports_the_last_insert_id() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER AUTO_INCREMENT PRIMARY KEY, name TEXT)")
+        .await?;
+
+    for id in 1..=3_i64 {
+        let result = sqlx::query("INSERT INTO users (name) VALUES (?)")
+            .bind("Alice")
+            .execute(&mut conn)
+            .await?;
+
+        assert_eq!(result.rows_affected(), 1);
+        assert_eq!(result.last_insert_id(), Some(id));
+    }
+
+    let result = conn.execute("UPDATE users SET name = 'Bob'").await?;
+
+    assert_eq!(result.rows_affected(), 3);
+    assert_eq!(result.last_insert_id(), None);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn it_selects_null() -> anyhow::Result<()> {
@@ -232,3 +261,90 @@ async fn test_fetch_one_and_ping() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_applies_session_settings_from_options() -> anyhow::Result<()> {
+    let options: MySqlConnectOptions = dotenv::var("DATABASE_URL")?.parse()?;
+    let options = options
+        .time_zone(Some("+02:00"))
+        .no_zero_dates(false)
+        .session_variable("wait_timeout", "1234")
+        .program_name("sqlx-tests");
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    let (time_zone, sql_mode, wait_timeout): (String, String, i64) =
+        sqlx::query_as("SELECT @@time_zone, @@sql_mode, CAST(@@wait_timeout AS SIGNED)")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(time_zone, "+02:00");
+    assert!(sql_mode.contains("PIPES_AS_CONCAT"));
+    assert_eq!(wait_timeout, 1234);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_evicts_cached_statements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.set_statement_cache_capacity(2);
+
+    for i in 0..5_i64 {
+        let (value,): (i64,) = sqlx::query_as(&format!("SELECT ? + {}", i))
+            .bind(1_i64)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+    }
+
+    assert_eq!(conn.cached_statements_size(), 2);
+
+    conn.clear_cached_statements().await?;
+
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    // the connection is still usable after closing its statements
+    let (value,): (i64,) = sqlx::query_as("SELECT ? + 1")
+        .bind(1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_prepares_statements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let statement = conn.prepare("SELECT ? + 1 AS value").await?;
+
+    assert_eq!(statement.param_types().len(), 1);
+    assert_eq!(statement.columns().len(), 1);
+    assert_eq!(statement.columns()[0].name.as_deref(), Some("value"));
+
+    for i in 0..3_i64 {
+        let (value,): (i64,) = statement.query_as().bind(i).fetch_one(&mut conn).await?;
+
+        assert_eq!(value, i + 1);
+    }
+
+    // the statement belongs to the connection that prepared it
+    let mut other = new::<MySql>().await?;
+
+    let result = statement.query().bind(1_i64).execute(&mut other).await;
+
+    assert!(matches!(
+        result,
+        Err(sqlx::Error::StatementConnectionMismatch)
+    ));
+
+    Ok(())
+}
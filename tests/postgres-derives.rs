@@ -325,3 +325,76 @@ async fn test_from_row_with_rename() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "macros")]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_with_flatten_default_and_skip() -> anyhow::Result<()> {
+    use sqlx::prelude::*;
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(rename_all = "camelCase")]
+    struct Address {
+        street_name: String,
+        city: String,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(rename_all = "camelCase")]
+    struct Account {
+        account_id: i32,
+
+        #[sqlx(flatten)]
+        address: Address,
+
+        #[sqlx(default)]
+        nickname: Option<String>,
+
+        #[sqlx(skip)]
+        visits: Vec<i32>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account: Account = sqlx::query_as(
+        r#"SELECT * from (VALUES (1, 'Main Street', 'Springfield')) accounts("accountId", "streetName", city)"#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.account_id);
+    assert_eq!("Main Street", account.address.street_name);
+    assert_eq!("Springfield", account.address.city);
+    assert_eq!(None, account.nickname);
+    assert!(account.visits.is_empty());
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_tuple_struct() -> anyhow::Result<()> {
+    use sqlx::prelude::*;
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Account(
+        i32,
+        #[sqlx(skip)] Vec<i32>,
+        String,
+        #[sqlx(default)] Option<String>,
+    );
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account: Account = sqlx::query_as("SELECT 1, 'Herp Derpinson'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(1, account.0);
+    assert!(account.1.is_empty());
+    assert_eq!("Herp Derpinson", account.2);
+    assert_eq!(None, account.3);
+
+    Ok(())
+}
@@ -11,9 +11,9 @@ use sqlx_test::new;
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn test_empty_query() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
-    let affected = conn.execute("").await?;
+    let result = conn.execute("").await?;
 
-    assert_eq!(affected, 0);
+    assert_eq!(result.rows_affected(), 0);
 
     Ok(())
 }
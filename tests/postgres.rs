@@ -1,7 +1,10 @@
 use futures::TryStreamExt;
-use sqlx::postgres::{PgPool, PgQueryAs, PgRow};
-use sqlx::{Connection, Cursor, Executor, Postgres, Row};
+use sqlx::pool::{CloseReason, Credentials, RetryPolicy, RoutedPool};
+use sqlx::postgres::{PgConnectOptions, PgPool, PgQueryAs, PgRow, PgTargetSessionAttrs};
+use sqlx::{Connect, Connection, Cursor, ErrorKind, Executor, PgConnection, Postgres, Row};
 use sqlx_test::new;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 // TODO: As soon as I tried to deserialize a json value in a function, inferance for this test stopped working. I am at a loss as to how to resolve this.
@@ -35,12 +38,14 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY);
         .await?;
 
     for index in 1..=10_i32 {
-        let cnt = sqlx::query("INSERT INTO users (id) VALUES ($1)")
+        let result = sqlx::query("INSERT INTO users (id) VALUES ($1)")
             .bind(index)
             .execute(&mut conn)
             .await?;
 
-        assert_eq!(cnt, 1);
+        assert_eq!(result.rows_affected(), 1);
+        assert_eq!(result.command(), Some("INSERT"));
+        assert_eq!(result.last_insert_id(), None);
     }
 
     let sum: i32 = sqlx::query("SELECT id FROM users")
@@ -88,6 +93,86 @@ async fn it_can_return_interleaved_nulls_issue_104() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_classifies_database_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE parents (id INTEGER PRIMARY KEY);
+CREATE TEMPORARY TABLE children (
+    id SERIAL PRIMARY KEY,
+    parent_id INTEGER NOT NULL REFERENCES parents (id),
+    age INTEGER CHECK (age >= 0)
+);
+INSERT INTO parents (id) VALUES (1);
+        "#,
+    )
+    .await?;
+
+    let cases = vec![
+        (
+            "INSERT INTO parents (id) VALUES (1)",
+            ErrorKind::UniqueViolation,
+        ),
+        (
+            "INSERT INTO children (parent_id) VALUES (2)",
+            ErrorKind::ForeignKeyViolation,
+        ),
+        (
+            "INSERT INTO children (parent_id) VALUES (NULL)",
+            ErrorKind::NotNullViolation,
+        ),
+        (
+            "INSERT INTO children (parent_id, age) VALUES (1, -1)",
+            ErrorKind::CheckViolation,
+        ),
+        ("SEELCT 1", ErrorKind::Other),
+    ];
+
+    for (query, kind) in cases {
+        match conn.execute(query).await {
+            Err(sqlx::Error::Database(error)) => assert_eq!(error.kind(), kind, "{}", query),
+            result => panic!("expected a database error, got {:?}", result),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reports_the_column_of_decode_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let error = sqlx::query("SELECT 1::INT AS id, 'text'::TEXT AS name")
+        .try_map(|row: PgRow| row.try_get::<i32, _>("name"))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    match error {
+        sqlx::Error::ColumnDecode { index, source } => {
+            assert_eq!(index, "\"name\"");
+            assert!(source.to_string().contains("i32"));
+            assert!(source.to_string().contains("TEXT"));
+        }
+
+        error => panic!("expected a column decode error, got {}", error),
+    }
+
+    let error = sqlx::query("SELECT 1::INT AS id, NULL::TEXT AS name")
+        .try_map(|row: PgRow| row.try_get::<String, _>(1))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::ColumnDecode { ref index, .. } if index == "1"));
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn it_can_work_with_transactions() -> anyhow::Result<()> {
@@ -154,6 +239,74 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_begins_transactions_with_options() -> anyhow::Result<()> {
+    use sqlx::{IsolationLevel, TransactionOptions};
+
+    let conn = new::<Postgres>().await?;
+
+    let mut tx = conn
+        .begin_with(TransactionOptions {
+            isolation: Some(IsolationLevel::Serializable),
+            read_only: true,
+            deferrable: true,
+        })
+        .await?;
+
+    let (isolation, read_only): (String, String) = sqlx::query_as(
+        "SELECT current_setting('transaction_isolation'), current_setting('transaction_read_only')",
+    )
+    .fetch_one(&mut tx)
+    .await?;
+
+    assert_eq!(isolation, "serializable");
+    assert_eq!(read_only, "on");
+
+    let result = tx
+        .execute("CREATE TEMPORARY TABLE options (id INTEGER)")
+        .await;
+
+    assert!(result.is_err());
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_retries_transactions_with_options() -> anyhow::Result<()> {
+    use sqlx::{IsolationLevel, TransactionOptions};
+
+    let pool = PgPool::builder()
+        .max_size(1)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let options = TransactionOptions {
+        isolation: Some(IsolationLevel::Serializable),
+        ..TransactionOptions::default()
+    };
+
+    let isolation = pool
+        .transaction_with_retry_with(&RetryPolicy::new(), options, |tx| {
+            Box::pin(async move {
+                let (isolation,): (String,) =
+                    sqlx::query_as("SELECT current_setting('transaction_isolation')")
+                        .fetch_one(tx)
+                        .await?;
+
+                Ok(isolation)
+            })
+        })
+        .await?;
+
+    assert_eq!(isolation, "serializable");
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
@@ -247,6 +400,61 @@ async fn it_can_rollback_nested_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_rolls_back_transactions_of_released_connections() -> anyhow::Result<()> {
+    // A single connection so that the next acquire gets the released connection
+    let pool = PgPool::builder()
+        .max_size(1)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS _sqlx_users_3952 (id INTEGER PRIMARY KEY)")
+        .execute(&pool)
+        .await?;
+
+    sqlx::query("TRUNCATE _sqlx_users_3952")
+        .execute(&pool)
+        .await?;
+
+    // A transaction that is neither committed nor rolled back
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("INSERT INTO _sqlx_users_3952 (id) VALUES ($1)")
+        .bind(10_i32)
+        .execute(&mut tx)
+        .await?;
+
+    assert!(tx.in_transaction());
+
+    drop(tx);
+
+    // A transaction that failed
+    let mut conn = pool.acquire().await?;
+
+    conn.execute("BEGIN").await?;
+
+    assert!(conn
+        .execute("SELECT * FROM _sqlx_not_a_table")
+        .await
+        .is_err());
+    assert!(conn.in_transaction());
+
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+
+    assert!(!conn.in_transaction());
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_3952")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
 // run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
 #[ignore]
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
@@ -383,3 +591,369 @@ async fn test_describe() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_evicts_cached_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.set_statement_cache_capacity(2);
+
+    for i in 0..5_i32 {
+        let (value,): (i32,) = sqlx::query_as(&format!("SELECT $1::int4 + {}", i))
+            .bind(1_i32)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+    }
+
+    assert_eq!(conn.cached_statements_size(), 2);
+
+    conn.clear_cached_statements().await?;
+
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    // the connection is still usable after closing its statements
+    let (value,): (i32,) = sqlx::query_as("SELECT $1::int4 + 1")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_prepares_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let statement = conn
+        .prepare("SELECT $1::int4 + 1 AS value, $2::text AS label")
+        .await?;
+
+    assert_eq!(statement.param_types().len(), 2);
+    assert_eq!(statement.columns().len(), 2);
+    assert_eq!(statement.columns()[0].name.as_deref(), Some("value"));
+    assert_eq!(statement.columns()[1].name.as_deref(), Some("label"));
+
+    for i in 0..3_i32 {
+        let (value, label): (i32, String) = statement
+            .query_as()
+            .bind(i)
+            .bind("label")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+        assert_eq!(label, "label");
+    }
+
+    // the statement belongs to the connection that prepared it
+    let mut other = new::<Postgres>().await?;
+
+    let result = statement
+        .query()
+        .bind(1_i32)
+        .bind("label")
+        .execute(&mut other)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(sqlx::Error::StatementConnectionMismatch)
+    ));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_connects_with_options() -> anyhow::Result<()> {
+    let options: PgConnectOptions = dotenv::var("DATABASE_URL")?.parse()?;
+    let options = options.statement_cache_capacity(1);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    for i in 0..3_i32 {
+        let (value,): (i32,) = sqlx::query_as(&format!("SELECT $1::int4 + {}", i))
+            .bind(1_i32)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+    }
+
+    assert_eq!(conn.cached_statements_size(), 1);
+
+    let pool = PgPool::builder().max_size(2).build_with(options).await?;
+
+    let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&pool).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_sends_startup_params() -> anyhow::Result<()> {
+    let options: PgConnectOptions = dotenv::var("DATABASE_URL")?.parse()?;
+    let options = options
+        .application_name("sqlx-test")
+        .options("-c search_path=sqlx_test")
+        .statement_timeout(Duration::from_secs(5));
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let (application_name, search_path, statement_timeout): (String, String, String) =
+        sqlx::query_as(
+            "SELECT current_setting('application_name'), current_setting('search_path'), current_setting('statement_timeout')",
+        )
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(application_name, "sqlx-test");
+    assert_eq!(search_path, "sqlx_test");
+    assert_eq!(statement_timeout, "5s");
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_checks_target_session_attrs() -> anyhow::Result<()> {
+    let options: PgConnectOptions = dotenv::var("DATABASE_URL")?.parse()?;
+
+    // The test database is a primary
+    for attrs in &[
+        PgTargetSessionAttrs::ReadWrite,
+        PgTargetSessionAttrs::Primary,
+        PgTargetSessionAttrs::PreferStandby,
+    ] {
+        let options = options.clone().target_session_attrs(*attrs);
+
+        PgConnection::connect_with(&options).await?.close().await?;
+    }
+
+    for attrs in &[
+        PgTargetSessionAttrs::ReadOnly,
+        PgTargetSessionAttrs::Standby,
+    ] {
+        let options = options.clone().target_session_attrs(*attrs);
+
+        assert!(PgConnection::connect_with(&options).await.is_err());
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_closes_broken_connections_on_release() -> anyhow::Result<()> {
+    let pool = PgPool::builder()
+        .max_size(2)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    let (pid,): (i32,) = sqlx::query_as("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&pool)
+        .await?;
+
+    assert!(sqlx::query("SELECT 1").execute(&mut conn).await.is_err());
+    assert!(conn.is_broken());
+
+    drop(conn);
+
+    let metrics = pool.metrics();
+
+    assert_eq!(metrics.connections_closed(CloseReason::Broken), 1);
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_routes_reads_to_healthy_replicas() -> anyhow::Result<()> {
+    let url = dotenv::var("DATABASE_URL")?;
+
+    let primary = PgPool::new(&url).await?;
+    let replica = PgPool::new(&url).await?;
+
+    let unreachable = PgPool::builder()
+        .connect_timeout(Duration::from_millis(500))
+        .build_with(PgConnectOptions::new().socket("/nonexistent"))
+        .await?;
+
+    let pool = RoutedPool::builder(primary)
+        .replica(unreachable)
+        .replica(replica)
+        .health_check_interval(None)
+        .build();
+
+    assert_eq!(pool.healthy_replicas(), 2);
+
+    pool.check_health().await;
+
+    assert_eq!(pool.healthy_replicas(), 1);
+
+    for _ in 0..3 {
+        let reader = pool.reader();
+
+        assert!(!std::ptr::eq(reader, pool.writer()));
+
+        let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(reader).await?;
+
+        assert_eq!(value, 1);
+    }
+
+    pool.reader().close().await;
+
+    // Without healthy replicas, reads go to the primary
+    assert_eq!(pool.healthy_replicas(), 0);
+    assert!(std::ptr::eq(pool.reader(), pool.writer()));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_keeps_saturated_replicas_healthy() -> anyhow::Result<()> {
+    let url = dotenv::var("DATABASE_URL")?;
+
+    let primary = PgPool::new(&url).await?;
+    let replica = PgPool::builder().max_size(1).build(&url).await?;
+
+    let unreachable = || {
+        PgPool::builder()
+            .connect_timeout(Duration::from_millis(500))
+            .build_lazy_with(PgConnectOptions::new().socket("/nonexistent"))
+    };
+
+    let pool = RoutedPool::builder(primary)
+        .replica(replica.clone())
+        .replica(unreachable())
+        .replica(unreachable())
+        .health_check_interval(None)
+        .health_check_timeout(Duration::from_millis(500))
+        .build();
+
+    // Every connection of the replica is busy serving a read
+    let _conn = replica.acquire().await?;
+
+    let start = std::time::Instant::now();
+    pool.check_health().await;
+
+    assert_eq!(pool.healthy_replicas(), 1);
+    assert!(!std::ptr::eq(pool.reader(), pool.writer()));
+
+    // The unreachable replicas are checked at the same time rather than one after another
+    assert!(
+        start.elapsed() < Duration::from_millis(900),
+        "health checks took {:?}",
+        start.elapsed()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_connects_with_provided_credentials() -> anyhow::Result<()> {
+    let url = dotenv::var("DATABASE_URL")?;
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let pool = PgPool::builder()
+        .max_size(2)
+        .credentials({
+            let calls = Arc::clone(&calls);
+
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+
+                Box::pin(async { Ok(Credentials::new()) })
+            }
+        })
+        .build(&url)
+        .await?;
+
+    let _a = pool.acquire().await?;
+    let _b = pool.acquire().await?;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // The credentials replace those of the URL
+    let pool = PgPool::builder()
+        .credentials(|| Box::pin(async { Ok(Credentials::new().username("sqlx_no_such_user")) }))
+        .build(&url)
+        .await?;
+
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::Database(_))
+    ));
+
+    // An error of the provider is returned from `acquire`
+    let pool = PgPool::builder()
+        .credentials(|| Box::pin(async { Err(sqlx::Error::Configuration("token expired".into())) }))
+        .build(&url)
+        .await?;
+
+    match pool.acquire().await {
+        Err(sqlx::Error::Configuration(error)) => assert_eq!(error.to_string(), "token expired"),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_builds_lazy_pools() -> anyhow::Result<()> {
+    let pool = PgPool::builder()
+        .min_size(2)
+        .build_lazy(&dotenv::var("DATABASE_URL")?)?;
+
+    assert_eq!(pool.size(), 0);
+
+    let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&pool).await?;
+
+    assert_eq!(value, 1);
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_retries_transient_connect_errors() -> anyhow::Result<()> {
+    let pool = PgPool::builder()
+        .connect_timeout(Duration::from_millis(500))
+        .connect_retry(
+            RetryPolicy::new()
+                .max_attempts(u32::MAX)
+                .backoff(Duration::from_millis(10), Duration::from_millis(50)),
+        )
+        .build_lazy_with(PgConnectOptions::new().socket("/nonexistent"));
+
+    // The socket does not exist, as if the database has not started yet
+    match pool.acquire().await {
+        // Every attempt failed the same way, so the error is returned as is
+        Err(sqlx::Error::Io(_)) => {}
+
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+
+    assert!(pool.metrics().connect_errors > 2);
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use sqlx::Sqlite;
-use sqlx_test::test_type;
+use sqlx_test::{new, test_type};
 use std::fmt::Debug;
 
 // Transparent types are rust-side wrappers over DB types
@@ -41,3 +41,176 @@ test_type!(weak_enum(
 ));
 
 test_type!(strong_color_enum(Sqlite, Color, "'green'" == Color::Green));
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_with_attributes() -> anyhow::Result<()> {
+    use sqlx::prelude::*;
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(rename_all = "UPPERCASE")]
+    struct Audit {
+        created_by: String,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(rename_all = "UPPERCASE")]
+    struct Account {
+        id: i32,
+
+        #[sqlx(rename = "display_name")]
+        name: String,
+
+        #[sqlx(flatten)]
+        audit: Audit,
+
+        #[sqlx(default)]
+        nickname: Option<String>,
+
+        #[sqlx(skip)]
+        visits: Vec<i32>,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let account: Account =
+        sqlx::query_as("SELECT 1 AS ID, 'Herp Derpinson' AS display_name, 'admin' AS CREATED_BY")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!("Herp Derpinson", account.name);
+    assert_eq!("admin", account.audit.created_by);
+    assert_eq!(None, account.nickname);
+    assert!(account.visits.is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_tuple_struct() -> anyhow::Result<()> {
+    use sqlx::prelude::*;
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Account(
+        i32,
+        #[sqlx(skip)] Vec<i32>,
+        String,
+        #[sqlx(default)] Option<String>,
+    );
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let account: Account = sqlx::query_as("SELECT 1, 'Herp Derpinson'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(1, account.0);
+    assert!(account.1.is_empty());
+    assert_eq!("Herp Derpinson", account.2);
+    assert_eq!(None, account.3);
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct AccountId(u64);
+
+impl std::convert::TryFrom<i64> for AccountId {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        std::convert::TryFrom::try_from(value).map(AccountId)
+    }
+}
+
+fn parse_color(value: String) -> Result<Color, String> {
+    match &*value {
+        "red" => Ok(Color::Red),
+        "green" => Ok(Color::Green),
+        "blue" => Ok(Color::Blue),
+        _ => Err(format!("unknown color {:?}", value)),
+    }
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_with_conversions() -> anyhow::Result<()> {
+    use sqlx::prelude::*;
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Account {
+        #[sqlx(try_from = "i64")]
+        id: AccountId,
+
+        #[sqlx(try_from = "String", with = "parse_color")]
+        color: Color,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let account: Account = sqlx::query_as("SELECT 10 AS id, 'blue' AS color")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(AccountId(10), account.id);
+    assert_eq!(Color::Blue, account.color);
+
+    let error = sqlx::query_as::<_, Account>("SELECT -1 AS id, 'blue' AS color")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::ColumnDecode { ref index, .. } if index == "\"id\""));
+
+    let error = sqlx::query_as::<_, Account>("SELECT 1 AS id, 'purple' AS color")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::ColumnDecode { ref index, .. } if index == "\"color\""));
+    assert!(error.to_string().contains("unknown color"));
+
+    Ok(())
+}
+
+fn parse_account_id(value: i64) -> Result<AccountId, std::num::TryFromIntError> {
+    std::convert::TryFrom::try_from(value)
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_with_inferred_conversions() -> anyhow::Result<()> {
+    use sqlx::prelude::*;
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Account {
+        #[sqlx(with = "parse_account_id")]
+        id: AccountId,
+
+        #[sqlx(with = "parse_color")]
+        color: Color,
+
+        name: String,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let account: Account = sqlx::query_as("SELECT 10 AS id, 'red' AS color, 'Herp' AS name")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(AccountId(10), account.id);
+    assert_eq!(Color::Red, account.color);
+    assert_eq!("Herp", account.name);
+
+    let error = sqlx::query_as::<_, Account>("SELECT 1 AS id, 'purple' AS color, '' AS name")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::ColumnDecode { ref index, .. } if index == "\"color\""));
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Load tests for the fairness of `Pool::acquire`, using Sqlite.
+
+use futures::future::join_all;
+use sqlx::SqlitePool;
+use std::time::{Duration, Instant};
+
+const MAX_SIZE: u32 = 4;
+
+// How long each task holds its connection
+const HOLD: Duration = Duration::from_millis(5);
+
+async fn sleep(duration: Duration) {
+    #[cfg(feature = "runtime-async-std")]
+    async_std::task::sleep(duration).await;
+
+    #[cfg(feature = "runtime-tokio")]
+    tokio::time::delay_for(duration).await;
+}
+
+async fn pool() -> anyhow::Result<SqlitePool> {
+    Ok(SqlitePool::builder()
+        .max_size(MAX_SIZE)
+        .connect_timeout(Duration::from_secs(10))
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?)
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_serves_waiters_in_order() -> anyhow::Result<()> {
+    let pool = pool().await?;
+
+    // Hold every connection so that the tasks below all wait
+    let held = join_all((0..MAX_SIZE).map(|_| pool.acquire())).await;
+
+    let waiters = (0..20_usize).map(|id| {
+        let pool = pool.clone();
+
+        async move {
+            // Each task starts waiting slightly after the previous one
+            sleep(Duration::from_millis(id as u64)).await;
+
+            let _conn = pool.acquire().await?;
+
+            sleep(HOLD).await;
+
+            Ok::<_, sqlx::Error>((id, Instant::now()))
+        }
+    });
+
+    let release = async {
+        // Let every task get in line before releasing the connections
+        sleep(Duration::from_millis(50)).await;
+        drop(held);
+    };
+
+    let (mut acquired, _) = futures::join!(join_all(waiters), release);
+
+    let mut order = acquired.drain(..).collect::<Result<Vec<_>, _>>()?;
+
+    order.sort_by_key(|(_, finished)| *finished);
+
+    // Tasks finish in the order they started waiting, give or take the connections that are
+    // released at the same time
+    for (position, (id, _)) in order.iter().enumerate() {
+        assert!(
+            (*id as isize - position as isize).abs() < MAX_SIZE as isize,
+            "task {} finished at position {}",
+            id,
+            position
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_bounds_the_wait_of_every_task() -> anyhow::Result<()> {
+    const TASKS: usize = 100;
+    const ROUNDS: usize = 5;
+
+    let pool = pool().await?;
+    let start = Instant::now();
+
+    // Many more tasks than connections, each acquiring repeatedly so that a task releasing a
+    // connection competes with the tasks already waiting for one
+    let tasks = (0..TASKS).map(|_| {
+        let pool = pool.clone();
+
+        async move {
+            let mut max_wait = Duration::from_secs(0);
+
+            for _ in 0..ROUNDS {
+                let waiting = Instant::now();
+                let _conn = pool.acquire().await?;
+
+                max_wait = max_wait.max(waiting.elapsed());
+
+                sleep(HOLD).await;
+            }
+
+            Ok::<_, sqlx::Error>(max_wait)
+        }
+    });
+
+    let waits = join_all(tasks)
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let elapsed = start.elapsed();
+    let max_wait = waits.iter().max().copied().unwrap_or_default();
+
+    // With fair queueing, a task waits for at most one connection use by each other task,
+    // spread over the connections of the pool. Twice that leaves room for the overhead of
+    // timers and scheduling; an unfair pool lets some tasks wait for several rounds
+    let fair_wait = HOLD * (TASKS as u32 / MAX_SIZE);
+
+    assert!(
+        max_wait < fair_wait * 2,
+        "longest wait {:?} of a run of {:?}, expected less than {:?}",
+        max_wait,
+        elapsed,
+        fair_wait * 2
+    );
+
+    let metrics = pool.metrics();
+
+    assert_eq!(metrics.acquire_timeouts, 0);
+    assert_eq!(metrics.acquired, (TASKS * ROUNDS) as u64);
+    assert!(metrics.size <= MAX_SIZE);
+
+    Ok(())
+}
@@ -1,6 +1,11 @@
 use futures::TryStreamExt;
-use sqlx::{sqlite::SqliteQueryAs, Connect, Connection, Executor, Sqlite, SqliteConnection};
+use sqlx::logging::{LogSettings, QueryEvent};
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteQueryAs},
+    Connect, Connection, ErrorKind, Executor, Sqlite, SqliteConnection, SqlitePool,
+};
 use sqlx_test::new;
+use std::sync::{Arc, Mutex};
 
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
@@ -37,13 +42,62 @@ async fn it_fails_to_parse() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_classifies_database_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute(
+        r#"
+PRAGMA foreign_keys = ON;
+CREATE TEMPORARY TABLE parents (id INTEGER PRIMARY KEY);
+CREATE TEMPORARY TABLE children (
+    id INTEGER PRIMARY KEY,
+    parent_id INTEGER NOT NULL REFERENCES parents (id),
+    age INTEGER CHECK (age >= 0)
+);
+INSERT INTO parents (id) VALUES (1);
+        "#,
+    )
+    .await?;
+
+    let cases = vec![
+        (
+            "INSERT INTO parents (id) VALUES (1)",
+            ErrorKind::UniqueViolation,
+        ),
+        (
+            "INSERT INTO children (parent_id) VALUES (2)",
+            ErrorKind::ForeignKeyViolation,
+        ),
+        (
+            "INSERT INTO children (parent_id) VALUES (NULL)",
+            ErrorKind::NotNullViolation,
+        ),
+        (
+            "INSERT INTO children (parent_id, age) VALUES (1, -1)",
+            ErrorKind::CheckViolation,
+        ),
+        ("SEELCT 1", ErrorKind::Other),
+    ];
+
+    for (query, kind) in cases {
+        match conn.execute(query).await {
+            Err(sqlx::Error::Database(error)) => assert_eq!(error.kind(), kind, "{}", query),
+            result => panic!("expected a database error, got {:?}", result),
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn it_handles_empty_queries() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
-    let affected = conn.execute("").await?;
+    let result = conn.execute("").await?;
 
-    assert_eq!(affected, 0);
+    assert_eq!(result.rows_affected(), 0);
 
     Ok(())
 }
@@ -62,12 +116,13 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)
         .await?;
 
     for index in 1..=10_i32 {
-        let cnt = sqlx::query("INSERT INTO users (id) VALUES (?)")
+        let result = sqlx::query("INSERT INTO users (id) VALUES (?)")
             .bind(index)
             .execute(&mut conn)
             .await?;
 
-        assert_eq!(cnt, 1);
+        assert_eq!(result.rows_affected(), 1);
+        assert_eq!(result.last_insert_id(), Some(i64::from(index)));
     }
 
     let sum: i32 = sqlx::query_as("SELECT id FROM users")
@@ -80,12 +135,46 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reports_the_id_of_inserts_into_several_tables() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE authors (id INTEGER PRIMARY KEY);
+CREATE TEMPORARY TABLE books (id INTEGER PRIMARY KEY);
+        "#,
+    )
+    .await?;
+
+    let result = conn.execute("INSERT INTO authors DEFAULT VALUES").await?;
+    assert_eq!(result.last_insert_id(), Some(1));
+
+    // The second table assigns the same ID as the first
+    let result = conn.execute("INSERT INTO books DEFAULT VALUES").await?;
+    assert_eq!(result.last_insert_id(), Some(1));
+
+    let result = conn.execute("UPDATE books SET id = 2").await?;
+    assert_eq!(result.rows_affected(), 1);
+    assert_eq!(result.last_insert_id(), None);
+
+    // Statements that insert nothing keep the ID of the last insert for later statements
+    let (id,): (i64,) = sqlx::query_as("SELECT last_insert_rowid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, 1);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn it_can_execute_multiple_statements() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
 
-    let affected = conn
+    let result = conn
         .execute(
             r#"
 CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, other INTEGER);
@@ -94,7 +183,14 @@ INSERT INTO users DEFAULT VALUES;
         )
         .await?;
 
-    assert_eq!(affected, 1);
+    let statements = result.statements();
+
+    assert_eq!(result.rows_affected(), 1);
+    assert_eq!(result.last_insert_id(), Some(1));
+    assert_eq!(statements.len(), 2);
+    assert_eq!(statements[0].rows_affected(), 0);
+    assert_eq!(statements[0].last_insert_id(), None);
+    assert_eq!(statements[1].rows_affected(), 1);
 
     for index in 2..5_i32 {
         let (id, other): (i32, i32) = sqlx::query_as(
@@ -114,6 +210,490 @@ SELECT id, other FROM users WHERE id = last_insert_rowid();
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reports_the_column_of_decode_errors() -> anyhow::Result<()> {
+    use sqlx::{Cursor, Row};
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut cursor = sqlx::query("SELECT 'text' AS name, NULL AS missing").fetch(&mut conn);
+    let row = cursor.next().await?.unwrap();
+
+    assert_eq!(row.try_get::<String, _>("name")?, "text");
+
+    match row.try_get::<String, _>(1) {
+        Err(sqlx::Error::ColumnDecode { index, source }) => {
+            assert_eq!(index, "1");
+            assert!(source
+                .downcast_ref::<sqlx::error::UnexpectedNullError>()
+                .is_some());
+        }
+
+        result => panic!("expected a column decode error, got {:?}", result),
+    }
+
+    assert!(matches!(
+        row.try_get::<i32, _>("unknown"),
+        Err(sqlx::Error::ColumnNotFound(_))
+    ));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_begins_transactions_with_options() -> anyhow::Result<()> {
+    use sqlx::{IsolationLevel, TransactionOptions};
+
+    let conn = new::<Sqlite>().await?;
+
+    let mut tx = conn
+        .begin_with(TransactionOptions {
+            isolation: Some(IsolationLevel::Serializable),
+            ..TransactionOptions::default()
+        })
+        .await?;
+
+    tx.execute("CREATE TEMPORARY TABLE options (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut conn = tx.commit().await?;
+
+    // The transaction has ended, so another one may begin
+    let tx = conn
+        .begin_with(TransactionOptions {
+            read_only: true,
+            ..TransactionOptions::default()
+        })
+        .await?;
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_runs_closures_in_transactions() -> anyhow::Result<()> {
+    use futures::FutureExt;
+    use std::panic::AssertUnwindSafe;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let affected = conn
+        .transaction(|conn| {
+            Box::pin(async move {
+                sqlx::query("INSERT INTO users (id) VALUES (1)")
+                    .execute(conn)
+                    .await
+            })
+        })
+        .await?;
+
+    assert_eq!(affected.rows_affected(), 1);
+
+    let result = conn
+        .transaction(|conn| {
+            Box::pin(async move {
+                sqlx::query("INSERT INTO users (id) VALUES (2)")
+                    .execute(conn)
+                    .await?;
+
+                Err::<(), _>(sqlx::Error::RowNotFound)
+            })
+        })
+        .await;
+
+    assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+
+    let panicked = AssertUnwindSafe(conn.transaction(|conn| {
+        Box::pin(async move {
+            sqlx::query("INSERT INTO users (id) VALUES (3)")
+                .execute(conn)
+                .await?;
+
+            panic!("the transaction panicked");
+
+            #[allow(unreachable_code)]
+            Ok(())
+        })
+    }))
+    .catch_unwind()
+    .await;
+
+    assert!(panicked.is_err());
+
+    // Only the committed transaction was kept
+    let ids: Vec<(i32,)> = sqlx::query_as("SELECT id FROM users")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, vec![(1,)]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_nests_closures_in_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE nested (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO nested (id) VALUES (1)")
+        .execute(&mut tx)
+        .await?;
+
+    let result = tx
+        .transaction(|tx| {
+            Box::pin(async move {
+                sqlx::query("INSERT INTO nested (id) VALUES (2)")
+                    .execute(tx)
+                    .await?;
+
+                Err::<(), _>(sqlx::Error::RowNotFound)
+            })
+        })
+        .await;
+
+    assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+    assert!(tx.in_transaction());
+
+    tx.transaction(|tx| {
+        Box::pin(async move {
+            sqlx::query("INSERT INTO nested (id) VALUES (3)")
+                .execute(&mut *tx)
+                .await?;
+
+            // A save point begun in the closure is nested in the closure's save point
+            let result = tx
+                .transaction(|tx| {
+                    Box::pin(async move {
+                        sqlx::query("INSERT INTO nested (id) VALUES (4)")
+                            .execute(tx)
+                            .await?;
+
+                        Err::<(), _>(sqlx::Error::RowNotFound)
+                    })
+                })
+                .await;
+
+            assert!(result.is_err());
+
+            Ok(())
+        })
+    })
+    .await?;
+
+    // Only the inner save points were rolled back, not the transaction
+    let mut conn = tx.commit().await?;
+
+    let ids: Vec<(i32,)> = sqlx::query_as("SELECT id FROM nested ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, vec![(1,), (3,)]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_begins_save_points_with_options() -> anyhow::Result<()> {
+    use sqlx::TransactionOptions;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE save_points (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let tx = conn.begin().await?;
+
+    let mut save_point = tx.begin_with(TransactionOptions::default()).await?;
+
+    sqlx::query("INSERT INTO save_points (id) VALUES (1)")
+        .execute(&mut save_point)
+        .await?;
+
+    // Rolling back the save point keeps the transaction open
+    let mut tx = save_point.rollback().await?;
+    assert!(tx.in_transaction());
+
+    let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM save_points")
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(count, (0,));
+
+    let result = tx
+        .begin_with(TransactionOptions {
+            read_only: true,
+            ..TransactionOptions::default()
+        })
+        .await;
+
+    assert!(matches!(result, Err(sqlx::Error::Configuration(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_retries_transactions() -> anyhow::Result<()> {
+    use sqlx::pool::RetryPolicy;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::time::Duration;
+
+    // A single connection so that each attempt sees the same temporary table
+    let pool = SqlitePool::builder()
+        .max_size(1)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    pool.acquire()
+        .await?
+        .execute("CREATE TEMPORARY TABLE attempts (attempt INTEGER NOT NULL)")
+        .await?;
+
+    let policy = RetryPolicy::new()
+        .max_attempts(3)
+        .backoff(Duration::from_millis(1), Duration::from_millis(5))
+        .retry_if(|error| matches!(error, sqlx::Error::RowNotFound));
+
+    let attempts = AtomicI32::new(0);
+
+    let attempt = pool
+        .transaction_with_retry(&policy, |tx| {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+
+            Box::pin(async move {
+                sqlx::query("INSERT INTO attempts (attempt) VALUES (?)")
+                    .bind(attempt)
+                    .execute(tx)
+                    .await?;
+
+                // Fail the first two attempts; their inserts are rolled back
+                if attempt < 3 {
+                    return Err(sqlx::Error::RowNotFound);
+                }
+
+                Ok(attempt)
+            })
+        })
+        .await?;
+
+    assert_eq!(attempt, 3);
+
+    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM attempts")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    // The error of the last attempt is returned once the attempts run out
+    let result = pool
+        .transaction_with_retry(&policy, |_| {
+            Box::pin(async move { Err::<(), _>(sqlx::Error::RowNotFound) })
+        })
+        .await;
+
+    assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_rolls_back_transactions_of_released_connections() -> anyhow::Result<()> {
+    // A single connection so that the next acquire gets the released connection
+    let pool = SqlitePool::builder()
+        .max_size(1)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    pool.acquire()
+        .await?
+        .execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    // A transaction begun with a plain statement
+    let mut conn = pool.acquire().await?;
+
+    conn.execute("BEGIN").await?;
+    conn.execute("INSERT INTO users (id) VALUES (1)").await?;
+
+    assert!(conn.in_transaction());
+
+    drop(conn);
+
+    // A transaction that is neither committed nor rolled back
+    let mut tx = pool.begin().await?;
+
+    assert!(tx.in_transaction());
+
+    tx.execute("INSERT INTO users (id) VALUES (2)").await?;
+
+    drop(tx);
+
+    let mut conn = pool.acquire().await?;
+
+    assert!(!conn.in_transaction());
+
+    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    // The connection was kept by the pool
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_runs_pool_hooks() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let connects = Arc::new(AtomicUsize::new(0));
+    let acquires = Arc::new(AtomicUsize::new(0));
+    let releases = Arc::new(AtomicUsize::new(0));
+
+    let (connected, acquired, released) = (
+        Arc::clone(&connects),
+        Arc::clone(&acquires),
+        Arc::clone(&releases),
+    );
+
+    // A single connection so that each acquire gets the released connection
+    let pool = SqlitePool::builder()
+        .max_size(1)
+        .after_connect(move |conn| {
+            connected.fetch_add(1, Ordering::SeqCst);
+
+            Box::pin(async move {
+                conn.execute("CREATE TEMPORARY TABLE events (name TEXT NOT NULL)")
+                    .await?;
+
+                Ok(())
+            })
+        })
+        .before_acquire(move |_| {
+            // The connection is closed on the second acquire of an idle connection
+            let vetoed = acquired.fetch_add(1, Ordering::SeqCst) == 1;
+
+            Box::pin(async move { Ok(!vetoed) })
+        })
+        .after_release(move |conn| {
+            released.fetch_add(1, Ordering::SeqCst);
+
+            Box::pin(async move {
+                conn.execute("DELETE FROM events").await?;
+
+                Ok(true)
+            })
+        })
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    pool.acquire()
+        .await?
+        .execute("INSERT INTO events (name) VALUES ('first')")
+        .await?;
+
+    // The same connection, reset by the release hook
+    let mut conn = pool.acquire().await?;
+
+    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM events")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+    assert_eq!(connects.load(Ordering::SeqCst), 1);
+
+    drop(conn);
+
+    // The idle connection is vetoed, so a new one is opened
+    let _conn = pool.acquire().await?;
+
+    assert_eq!(connects.load(Ordering::SeqCst), 2);
+    assert_eq!(acquires.load(Ordering::SeqCst), 2);
+    assert_eq!(releases.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_records_pool_metrics() -> anyhow::Result<()> {
+    use sqlx::pool::{CloseReason, PoolObserver};
+    use std::time::Duration;
+
+    struct Observer(Arc<Mutex<Vec<String>>>);
+
+    impl PoolObserver for Observer {
+        fn on_acquire_timeout(&self, _wait: Duration) {
+            self.0.lock().unwrap().push("timeout".to_owned());
+        }
+
+        fn on_connect(&self) {
+            self.0.lock().unwrap().push("connect".to_owned());
+        }
+
+        fn on_close(&self, reason: CloseReason) {
+            self.0.lock().unwrap().push(format!("close {:?}", reason));
+        }
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let pool = SqlitePool::builder()
+        .max_size(1)
+        .connect_timeout(Duration::from_millis(200))
+        .observer(Observer(Arc::clone(&events)))
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    // The only connection is checked out
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::PoolTimedOut(_))
+    ));
+
+    drop(conn);
+
+    pool.acquire().await?.close().await?;
+
+    let metrics = pool.metrics();
+
+    assert_eq!(metrics.size, 0);
+    assert_eq!(metrics.waiters, 0);
+    assert_eq!(metrics.acquired, 2);
+    assert_eq!(metrics.acquire_timeouts, 1);
+    assert_eq!(metrics.acquire_wait.count(), 3);
+    assert_eq!(metrics.connections_opened, 1);
+    assert_eq!(metrics.checkout_time.count(), 2);
+    assert_eq!(metrics.connections_closed(CloseReason::Closed), 1);
+    assert_eq!(metrics.total_connections_closed(), 1);
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec!["connect", "timeout", "close Closed"]
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn it_describes() -> anyhow::Result<()> {
@@ -210,3 +790,356 @@ CREATE TEMPORARY TABLE describe_test (
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_evicts_cached_statements() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:?statement-cache-capacity=2").await?;
+
+    for i in 0..5_i32 {
+        let (value,): (i32,) = sqlx::query_as(&format!("SELECT ?1 + {}", i))
+            .bind(1_i32)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+    }
+
+    assert_eq!(conn.cached_statements_size(), 2);
+
+    // the most recently used statement is still cached and can be re-used
+    let (value,): (i32,) = sqlx::query_as("SELECT ?1 + 4")
+        .bind(10_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 14);
+
+    conn.clear_cached_statements().await?;
+
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_prepares_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE prepared (id INTEGER NOT NULL, name TEXT)")
+        .await?;
+
+    let statement = conn
+        .prepare("INSERT INTO prepared (id, name) VALUES (?1, ?2)")
+        .await?;
+
+    assert_eq!(statement.param_types().len(), 2);
+    assert!(statement.columns().is_empty());
+
+    for id in 0..3_i32 {
+        statement
+            .query()
+            .bind(id)
+            .bind(format!("name {}", id))
+            .execute(&mut conn)
+            .await?;
+    }
+
+    let statement = conn
+        .prepare("SELECT id, name FROM prepared WHERE id = ?1")
+        .await?;
+
+    let columns = statement.columns();
+
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[0].name.as_deref(), Some("id"));
+    assert_eq!(columns[0].non_null, Some(true));
+    assert_eq!(columns[1].name.as_deref(), Some("name"));
+    assert_eq!(columns[1].non_null, Some(false));
+
+    let (id, name): (i32, String) = statement
+        .query_as()
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, 2);
+    assert_eq!(name, "name 2");
+
+    // the statement belongs to the connection that prepared it
+    let mut other = new::<Sqlite>().await?;
+
+    let result = statement.query().bind(1_i32).execute(&mut other).await;
+
+    assert!(matches!(
+        result,
+        Err(sqlx::Error::StatementConnectionMismatch)
+    ));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_passes_query_events_to_the_log_hook() -> anyhow::Result<()> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&events);
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.set_log_settings(
+        LogSettings::new()
+            .format_statements(false)
+            .log_arguments(true)
+            .redact_arguments_with(|_, index| index == 1)
+            .hook(move |event: &QueryEvent<'_>| {
+                recorded.lock().unwrap().push((
+                    event.sql.to_owned(),
+                    event.arguments.map(|arguments| arguments.join(", ")),
+                    event.rows_returned,
+                    event.rows_affected,
+                ));
+            }),
+    );
+
+    conn.execute("CREATE TEMPORARY TABLE logged (id INTEGER, secret TEXT)")
+        .await?;
+
+    sqlx::query("INSERT INTO logged (id, secret) VALUES (?1, ?2), (?1 + 1, ?2)")
+        .bind(1_i32)
+        .bind("hunter2")
+        .execute(&mut conn)
+        .await?;
+
+    let rows: Vec<(i32,)> = sqlx::query_as("SELECT id FROM logged")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+
+    let events = events.lock().unwrap();
+
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        events[1],
+        (
+            "INSERT INTO logged (id, secret) VALUES (?1, ?2), (?1 + 1, ?2)".to_owned(),
+            Some("1, <redacted>".to_owned()),
+            0,
+            2
+        )
+    );
+    assert_eq!(
+        events[2],
+        (
+            "SELECT id FROM logged".to_owned(),
+            Some(String::new()),
+            2,
+            0
+        )
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_connects_with_options() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::new()
+        .filename("tests/fixtures/does not exist.sqlite")
+        .create_if_missing(false);
+
+    assert!(SqliteConnection::connect_with(&options).await.is_err());
+
+    let options: SqliteConnectOptions = dotenv::var("DATABASE_URL")?.parse()?;
+    let pool = SqlitePool::builder()
+        .max_size(2)
+        .build_with(options.statement_cache_capacity(1))
+        .await?;
+
+    let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&pool).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_describes_the_pool_when_acquire_times_out() -> anyhow::Result<()> {
+    use sqlx::pool::PoolTimeout;
+    use std::time::Duration;
+
+    let pool = SqlitePool::builder()
+        .max_size(1)
+        .connect_timeout(Duration::from_millis(100))
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let _conn = pool.acquire().await?;
+
+    let source = match pool.acquire().await {
+        Err(sqlx::Error::PoolTimedOut(Some(source))) => source,
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    };
+
+    let timeout = source
+        .downcast_ref::<PoolTimeout>()
+        .expect("expected a PoolTimeout");
+
+    assert_eq!(timeout.size(), 1);
+    assert_eq!(timeout.max_size(), 1);
+    assert_eq!(timeout.idle(), 0);
+    assert_eq!(timeout.waiters(), 1);
+    assert!(timeout.elapsed() >= Duration::from_millis(100));
+    assert_eq!(timeout.last_connect_error(), None);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_resizes_a_running_pool() -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    let pool = SqlitePool::builder()
+        .max_size(3)
+        .connect_timeout(Duration::from_millis(100))
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conns = vec![
+        pool.acquire().await?,
+        pool.acquire().await?,
+        pool.acquire().await?,
+    ];
+
+    pool.set_max_size(1);
+    assert_eq!(pool.max_size(), 1);
+
+    drop(conns);
+
+    // the connections over the new maximum are closed in the background as they are returned
+    wait_for_size(&pool, 1).await;
+
+    let _conn = pool.acquire().await?;
+    assert!(pool.acquire().await.is_err());
+
+    // A task that is already waiting opens a connection once the pool may grow, well before
+    // its acquire would time out
+    let grow = async {
+        sleep(Duration::from_millis(20)).await;
+        pool.set_max_size(2);
+    };
+
+    let (conn, ()) = futures::join!(pool.acquire(), grow);
+
+    conn?;
+    assert_eq!(pool.max_size(), 2);
+
+    pool.set_min_size(1);
+    assert_eq!(pool.min_size(), 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_drains_a_pool() -> anyhow::Result<()> {
+    use std::time::{Duration, Instant};
+
+    let pool = SqlitePool::builder()
+        .max_size(2)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+    drop(pool.acquire().await?);
+
+    // the checked-out connection is not returned in time
+    assert!(!pool.drain(Instant::now() + Duration::from_millis(50)).await);
+    assert!(pool.is_closed());
+    assert_eq!(pool.size(), 1);
+    assert!(pool.acquire().await.is_err());
+
+    drop(conn);
+
+    assert!(pool.drain(Instant::now() + Duration::from_secs(5)).await);
+    assert_eq!(pool.size(), 0);
+
+    // a drain in progress is woken as soon as the last connection is returned
+    let pool = SqlitePool::builder()
+        .max_size(1)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+    let release = async {
+        sleep(Duration::from_millis(50)).await;
+        drop(conn);
+    };
+
+    let start = Instant::now();
+    let (drained, ()) = futures::join!(pool.drain(start + Duration::from_secs(30)), release);
+
+    assert!(drained);
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert_eq!(pool.size(), 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_closes_connections_older_than_an_instant() -> anyhow::Result<()> {
+    use std::time::Instant;
+
+    let pool = SqlitePool::builder()
+        .max_size(2)
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+    drop(pool.acquire().await?);
+    assert_eq!(pool.size(), 2);
+
+    pool.close_older_than(Instant::now());
+
+    // the idle connection is closed right away, the checked-out one once it is returned
+    wait_for_size(&pool, 1).await;
+
+    drop(conn);
+
+    wait_for_size(&pool, 0).await;
+
+    // new connections are not affected
+    drop(pool.acquire().await?);
+    assert_eq!(pool.size(), 1);
+    assert_eq!(pool.idle(), 1);
+
+    Ok(())
+}
+
+// Waits for connections that are closed in the background to leave the pool
+async fn wait_for_size(pool: &SqlitePool, size: u32) {
+    use std::time::Duration;
+
+    for _ in 0..100 {
+        if pool.size() == size {
+            break;
+        }
+
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(pool.size(), size);
+}
+
+async fn sleep(duration: std::time::Duration) {
+    #[cfg(feature = "runtime-async-std")]
+    async_std::task::sleep(duration).await;
+
+    #[cfg(feature = "runtime-tokio")]
+    tokio::time::delay_for(duration).await;
+}